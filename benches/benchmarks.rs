@@ -38,7 +38,7 @@ fn bench_get(c: &mut Criterion) {
     }
     c.bench_function("OmniMap, N=1e4, get", |b| {
         b.iter(|| {
-            black_box(map.get(&5000));
+            black_box(map.get(black_box(&5000)));
         })
     });
 }
@@ -50,7 +50,65 @@ fn bench_get_hashmap(c: &mut Criterion) {
     }
     c.bench_function("HashMap, N=1e4, get", |b| {
         b.iter(|| {
-            black_box(map.get(&5000));
+            black_box(map.get(black_box(&5000)));
+        })
+    });
+}
+
+fn bench_get_miss(c: &mut Criterion) {
+    let mut map = OmniMap::new();
+    for i in 0..10_000 {
+        map.insert(i, i);
+    }
+    c.bench_function("OmniMap, N=1e4, get all missing", |b| {
+        b.iter(|| {
+            for i in 10_000..20_000 {
+                black_box(map.get(&i));
+            }
+        })
+    });
+}
+
+fn bench_get_miss_hashmap(c: &mut Criterion) {
+    let mut map = HashMap::new();
+    for i in 0..10_000 {
+        map.insert(i, i);
+    }
+    c.bench_function("HashMap, N=1e4, get all missing", |b| {
+        b.iter(|| {
+            for i in 10_000..20_000 {
+                black_box(map.get(&i));
+            }
+        })
+    });
+}
+
+fn bench_get_strings(c: &mut Criterion) {
+    let keys: Vec<String> = (0..10_000).map(|i| format!("key-{}", i)).collect();
+    let mut map = OmniMap::new();
+    for (i, key) in keys.iter().enumerate() {
+        map.insert(key.clone(), i);
+    }
+    c.bench_function("OmniMap, N=1e4, get all with string keys", |b| {
+        b.iter(|| {
+            for key in &keys {
+                black_box(map.get(key.as_str()));
+            }
+        })
+    });
+}
+
+fn bench_get_strings_hashmap(c: &mut Criterion) {
+    let keys: Vec<String> = (0..10_000).map(|i| format!("key-{}", i)).collect();
+    let mut map = HashMap::new();
+    for (i, key) in keys.iter().enumerate() {
+        map.insert(key.clone(), i);
+    }
+    c.bench_function("HashMap, N=1e4, get all with string keys", |b| {
+        b.iter(|| {
+            for key in &keys {
+                black_box(map.get(key.as_str()));
+            }
         })
     });
 }
@@ -146,7 +204,7 @@ fn bench_clear(c: &mut Criterion) {
             map.insert(i, i.to_string());
         }
         b.iter(|| {
-            black_box(map.clear());
+            black_box(&mut map).clear();
         })
     });
 }
@@ -158,7 +216,74 @@ fn bench_clear_hashmap(c: &mut Criterion) {
             map.insert(i, i.to_string());
         }
         b.iter(|| {
-            black_box(map.clear());
+            black_box(&mut map).clear();
+        })
+    });
+}
+
+// Remove-heavy mixes, where the freed slots of the removed keys are on the probe sequences of the
+// remaining and the missing keys.
+
+fn bench_churn(c: &mut Criterion) {
+    c.bench_function("OmniMap, N=1e4, churn swap_remove + insert", |b| {
+        let mut map = OmniMap::new();
+        for i in 0..10_000 {
+            map.insert(i, i);
+        }
+        let mut next = 10_000;
+        b.iter(|| {
+            black_box(map.swap_remove(&(next - 10_000)));
+            black_box(map.insert(next, next));
+            next += 1;
+        })
+    });
+}
+
+fn bench_churn_hashmap(c: &mut Criterion) {
+    c.bench_function("HashMap, N=1e4, churn remove + insert", |b| {
+        let mut map = HashMap::new();
+        for i in 0..10_000 {
+            map.insert(i, i);
+        }
+        let mut next = 10_000;
+        b.iter(|| {
+            black_box(map.remove(&(next - 10_000)));
+            black_box(map.insert(next, next));
+            next += 1;
+        })
+    });
+}
+
+fn bench_get_after_remove(c: &mut Criterion) {
+    let mut map = OmniMap::new();
+    for i in 0..10_000 {
+        map.insert(i, i);
+    }
+    for i in (0..10_000).step_by(2) {
+        map.swap_remove(&i);
+    }
+    c.bench_function("OmniMap, N=1e4, get all after removing N/2", |b| {
+        b.iter(|| {
+            for i in 0..10_000 {
+                black_box(map.get(&i));
+            }
+        })
+    });
+}
+
+fn bench_get_after_remove_hashmap(c: &mut Criterion) {
+    let mut map = HashMap::new();
+    for i in 0..10_000 {
+        map.insert(i, i);
+    }
+    for i in (0..10_000).step_by(2) {
+        map.remove(&i);
+    }
+    c.bench_function("HashMap, N=1e4, get all after removing N/2", |b| {
+        b.iter(|| {
+            for i in 0..10_000 {
+                black_box(map.get(&i));
+            }
         })
     });
 }
@@ -170,7 +295,7 @@ fn bench_shrink_to(c: &mut Criterion) {
             map.insert(i, i);
         }
         b.iter(|| {
-            black_box(map.shrink_to(11_000));
+            black_box(&mut map).shrink_to(11_000);
         })
     });
 }
@@ -182,7 +307,7 @@ fn bench_shrink_to_hashmap(c: &mut Criterion) {
             map.insert(i, i);
         }
         b.iter(|| {
-            black_box(map.shrink_to(11_000));
+            black_box(&mut map).shrink_to(11_000);
         })
     });
 }
@@ -194,7 +319,7 @@ fn bench_shrink_to_fit(c: &mut Criterion) {
             map.insert(i, i);
         }
         b.iter(|| {
-            black_box(map.shrink_to_fit());
+            black_box(&mut map).shrink_to_fit();
         })
     });
 }
@@ -206,7 +331,7 @@ fn bench_shrink_to_fit_hashmap(c: &mut Criterion) {
             map.insert(i, i);
         }
         b.iter(|| {
-            black_box(map.shrink_to_fit());
+            black_box(&mut map).shrink_to_fit();
         })
     });
 }
@@ -215,7 +340,7 @@ fn bench_reserve(c: &mut Criterion) {
     c.bench_function("OmniMap, N=1e4, reserve", |b| {
         let mut map: OmniMap<i32, i32> = OmniMap::with_capacity(10_000);
         b.iter(|| {
-            black_box(map.reserve(10_000));
+            black_box(&mut map).reserve(10_000);
         })
     });
 }
@@ -261,6 +386,10 @@ criterion_group!(
     bench_insert_hashmap,
     bench_get,
     bench_get_hashmap,
+    bench_get_miss,
+    bench_get_miss_hashmap,
+    bench_get_strings,
+    bench_get_strings_hashmap,
     bench_first,
     bench_last,
 );
@@ -274,6 +403,10 @@ criterion_group!(
     bench_pop_last,
     bench_clear,
     bench_clear_hashmap,
+    bench_churn,
+    bench_churn_hashmap,
+    bench_get_after_remove,
+    bench_get_after_remove_hashmap,
 );
 
 criterion_group!(
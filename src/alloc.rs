@@ -2,11 +2,91 @@ use core::alloc::Layout;
 use core::marker::PhantomData;
 use core::ops::Range;
 use core::ptr;
+use core::ptr::NonNull;
 
 use crate::defer;
 use crate::error::{AllocError, OnError};
-use crate::opt::branch_prediction::likely;
-use std::alloc::{self, alloc};
+use alloc_crate::alloc::{alloc, dealloc};
+
+/// An implementation of `Allocator` allocates and deallocates the memory of the map's storage.
+///
+/// This trait is a stable subset of the unstable `core::alloc::Allocator`. With the feature
+/// `allocator_api` enabled on a nightly compiler, every implementation of
+/// `core::alloc::Allocator` implements this trait as well.
+///
+/// Allocators that are shared between maps, such as arenas, can implement this trait for a
+/// shared reference like `&Arena`, and the map will hold the reference.
+///
+/// # Safety
+///
+/// - Memory blocks returned by [`Allocator::allocate`] must remain valid until they are passed
+///   to [`Allocator::deallocate`] of the same allocator, or until the allocator is dropped.
+///
+/// - Moving the allocator must not invalidate its allocated memory blocks.
+///
+/// - Returned memory blocks must fit the requested layout, including its alignment.
+pub unsafe trait Allocator {
+    /// Allocates a memory block that fits `layout`.
+    ///
+    /// The size of `layout` is always greater than `0`.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(NonNull<u8>)`: A pointer to the start of the allocated memory block.
+    /// `Err(AllocError)`: If the allocation was unsuccessful.
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError>;
+
+    /// Deallocates the memory block pointed to by `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// - `ptr` must have been allocated by this allocator.
+    ///
+    /// - `layout` must be the same layout used to allocate the memory block.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+/// The global memory allocator.
+///
+/// This type forwards the calls to the allocator registered with the `#[global_allocator]`
+/// attribute, or the default allocator of the `std` crate.
+///
+/// In `no_std` builds, the final binary must register a global allocator.
+///
+/// This is the default allocator of [`OmniMap`](crate::OmniMap).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Global;
+
+unsafe impl Allocator for Global {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        NonNull::new(unsafe { alloc(layout) }).ok_or(AllocError::AllocatorErr)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        dealloc(ptr.as_ptr(), layout)
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+unsafe impl<A> Allocator for A
+where
+    A: core::alloc::Allocator,
+{
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        match core::alloc::Allocator::allocate(self, layout) {
+            Ok(block) => Ok(block.cast()),
+            Err(_) => Err(AllocError::AllocatorErr),
+        }
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        core::alloc::Allocator::deallocate(self, ptr, layout)
+    }
+}
 
 /// Debug-mode check for the valid alignment.
 /// This function is only available in debug builds.
@@ -113,9 +193,9 @@ const fn debug_assert_copy_inbounds(allocated_count: usize, copy_count: usize) {
 ///
 /// Limited checks for invariants are done in debug mode only.
 ///
-/// This pointer uses the registered `#[global_allocator]` to allocate memory.
-///
-/// Using custom allocators will be supported in the future.
+/// This pointer doesn't store the allocator, the allocator is provided by the caller to the
+/// methods that allocate and deallocate memory, and it must be the same allocator for the
+/// lifetime of the allocation.
 pub(crate) struct UnsafeBufferPointer<T> {
     ptr: *const T,
     _marker: PhantomData<T>,
@@ -216,26 +296,26 @@ impl<T> UnsafeBufferPointer<T> {
         Err(on_err.overflow())
     }
 
-    /// Allocates memory space according to the provided `layout`.
+    /// Allocates memory space according to the provided `layout` using the allocator `alloc`.
     ///
     /// This method handles allocation error according to the error handling context `on_err`.
     ///
     /// Note that the process may be terminated even if the allocation was successful, because
     /// detecting memory allocation failures at the process-level is platform-specific.
     ///
-    /// For instance, on some systems like linux, overcommit is allowed by default, which means 
-    /// that the kernel will map virtual memory to the process regardless of the backing memory, 
-    /// only to invoke the so-called _OOM killer_ later, and the process may become a target for 
+    /// For instance, on some systems like linux, overcommit is allowed by default, which means
+    /// that the kernel will map virtual memory to the process regardless of the backing memory,
+    /// only to invoke the so-called _OOM killer_ later, and the process may become a target for
     /// termination.
     ///
-    /// For better safety, consult the platform-specific documentation regarding out-of-memory 
+    /// For better safety, consult the platform-specific documentation regarding out-of-memory
     /// (OOM) behavior.
     ///
     /// # Safety
     ///
     /// - Pointer must be `null` before calling this method.
     ///   This method doesn't deallocate the allocated memory space pointed to by this pointer.
-    ///   Calling this method with a non-null pointer causes memory leaks, as access to the 
+    ///   Calling this method with a non-null pointer causes memory leaks, as access to the
     ///   allocated memory space will be lost without freeing it.
     ///
     /// - `align` must be a power of 2.
@@ -249,8 +329,9 @@ impl<T> UnsafeBufferPointer<T> {
     ///
     /// `Ok(())`: If the allocation was successful.
     /// `Err(AllocError)`: If the allocation was unsuccessful.
-    pub(crate) unsafe fn allocate(
+    pub(crate) unsafe fn allocate<A: Allocator>(
         &mut self,
+        alloc: &A,
         layout: Layout,
         on_err: OnError,
     ) -> Result<(), AllocError> {
@@ -260,10 +341,8 @@ impl<T> UnsafeBufferPointer<T> {
         #[cfg(debug_assertions)]
         debug_layout_size_align(layout.size(), layout.align());
 
-        let ptr = alloc(layout) as *mut T;
-
-        if likely(!ptr.is_null()) {
-            self.ptr = ptr;
+        if let Ok(ptr) = alloc.allocate(layout) {
+            self.ptr = ptr.as_ptr() as *mut T;
             return Ok(());
         }
 
@@ -286,14 +365,16 @@ impl<T> UnsafeBufferPointer<T> {
     ///   dropped properly before calling this method.
     ///
     /// - `layout` must be the same layout used to allocate the memory space.
-    pub(crate) unsafe fn deallocate(&mut self, layout: Layout) {
+    ///
+    /// - `alloc` must be the same allocator used to allocate the memory space.
+    pub(crate) unsafe fn deallocate<A: Allocator>(&mut self, alloc: &A, layout: Layout) {
         #[cfg(debug_assertions)]
         debug_assert_allocated(self);
 
         #[cfg(debug_assertions)]
         debug_layout_size_align(layout.size(), layout.align());
 
-        alloc::dealloc(self.ptr as *mut u8, layout);
+        alloc.deallocate(NonNull::new_unchecked(self.ptr as *mut u8), layout);
 
         self.ptr = ptr::null();
     }
@@ -314,6 +395,8 @@ impl<T> UnsafeBufferPointer<T> {
     /// - `allocated` layout must be the same as the previously allocated layout of type `T`.
     ///   If the layout is not the same, the result is `undefined behavior`.
     ///
+    /// - `alloc` must be the same allocator used to allocate the memory space.
+    ///
     /// - Initialized elements will not be dropped when shrinking the memory space.
     ///   This might cause memory leaks if `T` is not of trivial type, or if the elements are not
     ///   dropped properly before calling this method.
@@ -334,8 +417,9 @@ impl<T> UnsafeBufferPointer<T> {
     ///
     /// `Ok(())`: If the allocation was successful.
     /// `Err(AllocError)`: If the allocation was unsuccessful.
-    pub(crate) unsafe fn reallocate(
+    pub(crate) unsafe fn reallocate<A: Allocator>(
         &mut self,
+        alloc: &A,
         allocated: Layout,
         new: Layout,
         copy_count: usize,
@@ -353,12 +437,12 @@ impl<T> UnsafeBufferPointer<T> {
         #[cfg(debug_assertions)]
         debug_assert_copy_inbounds(allocated.size() / Self::T_SIZE, copy_count);
 
-        let new_ptr = alloc(new) as *mut T;
+        if let Ok(new_ptr) = alloc.allocate(new) {
+            let new_ptr = new_ptr.as_ptr() as *mut T;
 
-        if likely(!new_ptr.is_null()) {
             ptr::copy_nonoverlapping(self.ptr, new_ptr, copy_count);
 
-            alloc::dealloc(self.ptr as *mut u8, allocated);
+            alloc.deallocate(NonNull::new_unchecked(self.ptr as *mut u8), allocated);
 
             self.ptr = new_ptr;
 
@@ -584,6 +668,61 @@ impl<T> UnsafeBufferPointer<T> {
         ptr::copy(src, dst, count);
     }
 
+    /// Shifts the `count` values starting from `at` to the right, leaving the value at `at`
+    /// unaffected as a bitwise copy.
+    ///
+    /// The value at `at + count` will be overwritten **without** calling `drop`.
+    ///
+    /// # Safety
+    ///
+    /// - Pointer must be allocated before calling this method.
+    ///   Calling this method with a null ptr will cause termination with `SIGABRT`.
+    ///
+    /// - `at + count` must be within the bounds of the allocated memory space.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n) where `n` is the number (`count`) of the elements to be shifted.
+    #[inline(always)]
+    pub const unsafe fn shift_right(&mut self, at: usize, count: usize) {
+        #[cfg(debug_assertions)]
+        debug_assert_allocated(self);
+
+        let src = (self.ptr as *mut T).add(at);
+        let dst = src.add(1);
+
+        ptr::copy(src, dst, count);
+    }
+
+    /// Copies `count` values starting from the offset `from` to the offset `to`, overwriting the
+    /// values at the destination and leaving the source values unaffected.
+    ///
+    /// The source and destination ranges may overlap.
+    ///
+    /// # Safety
+    ///
+    /// - Pointer must be allocated before calling this method.
+    ///   Calling this method with a null ptr will cause termination with `SIGABRT`.
+    ///
+    /// - `from + count` and `to + count` must be within the bounds of the allocated memory space.
+    ///
+    /// - Initialized values in the destination range will be overwritten **without** calling
+    ///   `drop`.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n) where `n` is the number (`count`) of the elements to be copied.
+    #[inline(always)]
+    pub const unsafe fn memmove(&mut self, from: usize, to: usize, count: usize) {
+        #[cfg(debug_assertions)]
+        debug_assert_allocated(self);
+
+        let src = (self.ptr as *mut T).add(from);
+        let dst = (self.ptr as *mut T).add(to);
+
+        ptr::copy(src, dst, count);
+    }
+
     /// Copies the value at the offset `from` to the offset `to`, overwriting the value at `to`
     /// and leaving the value at `from` unaffected.
     ///
@@ -781,7 +920,9 @@ impl<T> UnsafeBufferPointer<T> {
 ///
 /// This implementation is a debug-mode check to ensure that the allocated memory space is
 /// deallocated before dropping the `UnsafeBufferPointer`.
-#[cfg(debug_assertions)]
+///
+/// The check requires `std` to detect unwinding, so it is not available in `no_std` builds.
+#[cfg(all(debug_assertions, feature = "std"))]
 impl<T> Drop for UnsafeBufferPointer<T> {
     fn drop(&mut self) {
         // The `drop` method is called automatically when the thread is panicking.
@@ -881,12 +1022,12 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             // Memory space should have been allocated.
             assert!(!buffer_ptr.is_null());
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -896,12 +1037,12 @@ mod ptr_tests {
 
         unsafe {
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let result = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let result = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             assert!(result.is_ok());
             assert!(!buffer_ptr.is_null());
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -914,12 +1055,12 @@ mod ptr_tests {
         unsafe {
             let layout = buffer_ptr.make_layout_unchecked(1);
             // Not yet allocated, should not panic.
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             assert!(!buffer_ptr.is_null());
 
             // Already allocated, should panic.
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
         }
     }
 
@@ -930,11 +1071,11 @@ mod ptr_tests {
 
             let layout = buffer_ptr.make_layout_unchecked(3);
 
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             assert!(!buffer_ptr.is_null());
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
 
             assert!(buffer_ptr.is_null());
         }
@@ -945,7 +1086,7 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             *(buffer_ptr.ptr as *mut u8) = 1;
             *(buffer_ptr.ptr as *mut u8).add(1) = 2;
@@ -954,7 +1095,7 @@ mod ptr_tests {
             let new_layout = buffer_ptr.make_layout(5, OnError::NoReturn).unwrap();
 
             // Grows the count to 5.
-            let result = buffer_ptr.reallocate(layout, new_layout, 3, OnError::NoReturn);
+            let result = buffer_ptr.reallocate(&Global, layout, new_layout, 3, OnError::NoReturn);
             assert!(result.is_ok());
 
             // Read values after reallocation.
@@ -962,7 +1103,7 @@ mod ptr_tests {
                 assert_eq!(*buffer_ptr.ptr.add(i), i as u8 + 1);
             }
 
-            buffer_ptr.deallocate(new_layout);
+            buffer_ptr.deallocate(&Global, new_layout);
         }
     }
 
@@ -977,7 +1118,8 @@ mod ptr_tests {
             let allocated_layout = buffer_ptr.make_layout(5, OnError::NoReturn).unwrap();
             let new_layout = buffer_ptr.make_layout(10, OnError::NoReturn).unwrap();
 
-            let _ = buffer_ptr.reallocate(allocated_layout, new_layout, 5, OnError::NoReturn);
+            let _ =
+                buffer_ptr.reallocate(&Global, allocated_layout, new_layout, 5, OnError::NoReturn);
         }
     }
 
@@ -986,7 +1128,7 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             for i in 0..3 {
                 buffer_ptr.store(i, i as u8 + 1);
@@ -1002,7 +1144,7 @@ mod ptr_tests {
                 assert_eq!(*buffer_ptr.load(i), 0);
             }
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1011,7 +1153,7 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             // Store some values.
             for i in 0..3 {
@@ -1022,7 +1164,7 @@ mod ptr_tests {
             assert_eq!(*buffer_ptr.load(1), 2);
             assert_eq!(*buffer_ptr.load(2), 3);
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1031,7 +1173,7 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             // Store some values.
             buffer_ptr.store(0, 1);
@@ -1043,7 +1185,7 @@ mod ptr_tests {
             // Value should be updated.
             assert_eq!(*buffer_ptr.load(0), 10);
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1052,14 +1194,14 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             buffer_ptr.store(0, 1);
             buffer_ptr.store(1, 2);
 
             assert_eq!(buffer_ptr.load_first(), &1);
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1068,7 +1210,7 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             buffer_ptr.store(0, 1);
             buffer_ptr.store(1, 2);
@@ -1077,7 +1219,7 @@ mod ptr_tests {
 
             assert_eq!(*buffer_ptr.load(1), 2);
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1086,7 +1228,7 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(5);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             for i in 0..5 {
                 buffer_ptr.store(i, i as u8 + 1);
@@ -1100,7 +1242,54 @@ mod ptr_tests {
             assert_eq!(*buffer_ptr.load(3), 5);
             assert_eq!(*buffer_ptr.load(4), 5);
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
+        }
+    }
+
+    #[test]
+    fn test_buffer_ptr_shift_right() {
+        unsafe {
+            let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
+            let layout = buffer_ptr.make_layout_unchecked(5);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
+
+            for i in 0..5 {
+                buffer_ptr.store(i, i as u8 + 1);
+            }
+
+            buffer_ptr.shift_right(1, 3);
+
+            assert_eq!(*buffer_ptr.load(0), 1);
+            assert_eq!(*buffer_ptr.load(1), 2);
+            assert_eq!(*buffer_ptr.load(2), 2);
+            assert_eq!(*buffer_ptr.load(3), 3);
+            assert_eq!(*buffer_ptr.load(4), 4);
+
+            buffer_ptr.deallocate(&Global, layout);
+        }
+    }
+
+    #[test]
+    fn test_buffer_ptr_memmove() {
+        unsafe {
+            let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
+            let layout = buffer_ptr.make_layout_unchecked(5);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
+
+            for i in 0..5 {
+                buffer_ptr.store(i, i as u8 + 1);
+            }
+
+            // Overlapping ranges.
+            buffer_ptr.memmove(2, 0, 3);
+
+            assert_eq!(*buffer_ptr.load(0), 3);
+            assert_eq!(*buffer_ptr.load(1), 4);
+            assert_eq!(*buffer_ptr.load(2), 5);
+            assert_eq!(*buffer_ptr.load(3), 4);
+            assert_eq!(*buffer_ptr.load(4), 5);
+
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1109,7 +1298,7 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             buffer_ptr.store(0, 10);
             buffer_ptr.store(1, 20);
@@ -1121,7 +1310,7 @@ mod ptr_tests {
             assert_eq!(*buffer_ptr.load(1), 20);
             assert_eq!(*buffer_ptr.load(2), 10); // Value at index 2 is overwritten.
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1131,7 +1320,7 @@ mod ptr_tests {
     fn test_buffer_ptr_as_slice_null_ptr() {
         let buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
         let slice = unsafe { buffer_ptr.as_slice(0) };
-        assert_eq!(slice, &[]);
+        assert_eq!(slice, &[] as &[u8]);
     }
 
     #[test]
@@ -1139,13 +1328,13 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             let slice = buffer_ptr.as_slice(0);
-            assert_eq!(slice, &[]);
+            assert_eq!(slice, &[] as &[u8]);
 
             // Deallocate memory space or the destructor will panic.
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1154,7 +1343,7 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             // Store some values.
             for i in 0..3 {
@@ -1165,7 +1354,7 @@ mod ptr_tests {
             let slice = buffer_ptr.as_slice(3);
             assert_eq!(slice, &[1, 2, 3]);
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1175,7 +1364,7 @@ mod ptr_tests {
     fn test_buffer_ptr_as_slice_mut_null_ptr() {
         let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
         let slice = unsafe { buffer_ptr.as_slice_mut(0) };
-        assert_eq!(slice, &mut []);
+        assert_eq!(slice, &mut [] as &mut [u8]);
     }
 
     #[test]
@@ -1183,13 +1372,13 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             let slice = buffer_ptr.as_slice_mut(0);
-            assert_eq!(slice, &[]);
+            assert_eq!(slice, &[] as &[u8]);
 
             // Deallocate memory space or the destructor will panic.
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1198,7 +1387,7 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             // Store some values.
             for i in 0..3 {
@@ -1209,7 +1398,7 @@ mod ptr_tests {
             let slice = buffer_ptr.as_slice_mut(3);
             assert_eq!(slice, &mut [1, 2, 3]);
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1232,7 +1421,7 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<DropCounter> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(3);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             // Reference 5 elements to the same drop counter.
             for i in 0..3 {
@@ -1254,7 +1443,7 @@ mod ptr_tests {
             // `drop` should have been called on all elements, so the drop count must be 3.
             assert_eq!(*drop_count.borrow(), 3);
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1266,7 +1455,7 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(5);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
             buffer_ptr.drop_range(0..0);
         }
     }
@@ -1280,7 +1469,7 @@ mod ptr_tests {
         unsafe {
             let mut buffer_ptr: UnsafeBufferPointer<DropCounter> = UnsafeBufferPointer::new();
             let layout = buffer_ptr.make_layout_unchecked(5);
-            let _ = buffer_ptr.allocate(layout, OnError::NoReturn);
+            let _ = buffer_ptr.allocate(&Global, layout, OnError::NoReturn);
 
             // Reference 5 elements to the same drop counter.
             for i in 0..5 {
@@ -1298,7 +1487,7 @@ mod ptr_tests {
             // Since the `drop` has been called on 3 elements, the drop count must be 3.
             assert_eq!(*drop_count.borrow(), 3);
 
-            buffer_ptr.deallocate(layout);
+            buffer_ptr.deallocate(&Global, layout);
         }
     }
 
@@ -1307,14 +1496,14 @@ mod ptr_tests {
         unsafe {
             let mut source: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
             let layout = source.make_layout_unchecked(3);
-            let _ = source.allocate(layout, OnError::NoReturn);
+            let _ = source.allocate(&Global, layout, OnError::NoReturn);
 
             for i in 0..3 {
                 source.store(i, i as u8 + 1);
             }
 
             let mut target: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
-            let _ = target.allocate(layout, OnError::NoReturn);
+            let _ = target.allocate(&Global, layout, OnError::NoReturn);
 
             target.clone_from(source.ptr, 3);
 
@@ -1322,8 +1511,8 @@ mod ptr_tests {
                 assert_eq!(*source.load(i), *target.load(i));
             }
 
-            source.deallocate(layout);
-            target.deallocate(layout);
+            source.deallocate(&Global, layout);
+            target.deallocate(&Global, layout);
         }
     }
 
@@ -1359,8 +1548,8 @@ mod ptr_tests {
         let mut target: UnsafeBufferPointer<PanicOnClone> = UnsafeBufferPointer::new();
         unsafe {
             let layout = source.make_layout_unchecked(10);
-            let _ = source.allocate(layout, OnError::NoReturn);
-            let _ = target.allocate(layout, OnError::NoReturn);
+            let _ = source.allocate(&Global, layout, OnError::NoReturn);
+            let _ = target.allocate(&Global, layout, OnError::NoReturn);
 
             let drop_counter = Rc::new(RefCell::new(0));
             for i in 0..10 {
@@ -1387,20 +1576,20 @@ mod ptr_tests {
             assert!(result.is_err());
             assert_eq!(*drop_counter.borrow(), 5);
 
-            source.deallocate(layout);
-            target.deallocate(layout);
+            source.deallocate(&Global, layout);
+            target.deallocate(&Global, layout);
         }
     }
 
     #[test]
-    #[cfg(debug_assertions)]
+    #[cfg(all(debug_assertions, feature = "std"))]
     #[should_panic(expected = "Pointer must be deallocated before dropping")]
     #[cfg_attr(miri, ignore)]
     fn test_buffer_ptr_drop() {
         let mut source: UnsafeBufferPointer<u8> = UnsafeBufferPointer::new();
         unsafe {
             let layout = source.make_layout_unchecked(1);
-            let _ = source.allocate(layout, OnError::NoReturn);
+            let _ = source.allocate(&Global, layout, OnError::NoReturn);
         }
 
         // Dropping the pointer without deallocating the memory space should panic.
@@ -48,6 +48,7 @@
 /// assert_eq!(dict.get(&"two"), Some(&2));
 /// assert_eq!(dict.get(&"three"), Some(&3));
 /// ```
+#[cfg(feature = "std")]
 #[macro_export]
 macro_rules! map {
     // Pattern without explicit capacity.
@@ -86,7 +87,84 @@ macro_rules! map {
     };
 }
 
-#[cfg(test)]
+/// A declarative constructor that creates a set from a group of values.
+///
+/// # Examples
+///
+/// This example creates a set without specifying the capacity.
+///
+/// > Note: The set will be created with a pre-allocated capacity equal to the number of values to
+/// > avoid resizing.
+///
+/// ```
+/// use omnimap::omni_set;
+///
+/// let set = omni_set! { "one", "two", "three", "two" };
+///
+/// assert_eq!(set.len(), 3);
+/// assert_eq!(set.capacity(), 4);
+///
+/// assert_eq!(set.get_index_of(&"three"), Some(2));
+/// ```
+///
+/// This example creates a set with the specified capacity.
+///
+/// The capacity is specified before the values.
+///
+/// ```
+/// use omnimap::omni_set;
+///
+/// let set = omni_set! {
+///   10; // Capacity
+///   "one",
+///   "two",
+/// };
+///
+/// assert_eq!(set.len(), 2);
+/// assert_eq!(set.capacity(), 10);
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! omni_set {
+    // Pattern without explicit capacity.
+    ( $( $value:expr ),* $(,)? ) => {
+        {
+            use $crate::OmniSet;
+
+            const V_COUNT: usize = <[&str]>::len(&[$(stringify!($value)),*]);
+
+            #[allow(unused_mut)]
+            let mut set = OmniSet::with_capacity(V_COUNT);
+            $(
+                set.insert($value);
+            )*
+            set
+        }
+    };
+    // Pattern with explicit capacity.
+    ( $capacity:expr; $( $value:expr ),* $(,)? ) => {
+        {
+            use $crate::OmniSet;
+
+            const V_COUNT: usize = <[&str]>::len(&[$(stringify!($value)),*]);
+
+            const CAPACITY: usize = if $capacity > V_COUNT { $capacity } else { V_COUNT };
+
+            #[allow(unused_mut)]
+            let mut set = OmniSet::with_capacity(CAPACITY);
+            $(
+                set.insert($value);
+            )*
+            set
+        }
+    };
+    // Anything else is invalid.
+    ( $($tt:tt)* ) => {
+        compile_error!("Invalid syntax. Use `omni_set! { value, ... }` or `omni_set! { capacity; value, ... }`.");
+    };
+}
+
+#[cfg(all(test, feature = "std"))]
 mod builder_tests {
     #[test]
     fn test_builder_without_capacity() {
@@ -120,4 +198,30 @@ mod builder_tests {
         assert_eq!(dict.get(&"two"), Some(&2));
         assert_eq!(dict.get(&"three"), Some(&3));
     }
+
+    #[test]
+    fn test_set_builder_without_capacity() {
+        let set = omni_set! { 3, 1, 2, 1 };
+
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.capacity(), 4);
+        assert_eq!(set.iter().copied().collect::<Vec<i32>>(), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn test_set_builder_with_capacity() {
+        let set = omni_set! {
+            10; // Capacity
+            "one",
+            "two",
+        };
+
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.capacity(), 10);
+        assert!(set.contains(&"one"));
+        assert!(set.contains(&"two"));
+
+        let empty: crate::OmniSet<u8> = omni_set! {};
+        assert!(empty.is_empty());
+    }
 }
@@ -0,0 +1,400 @@
+use core::fmt;
+use core::fmt::Debug;
+use core::hash::{BuildHasher, Hash};
+use core::mem;
+
+use crate::alloc::{Allocator, Global};
+use crate::hash::DefaultHashBuilder;
+use crate::map::OmniMap;
+
+/// A view into a single entry in the map, which may either be occupied or vacant.
+///
+/// This type is constructed by the [`OmniMap::entry`] method.
+pub enum Entry<'a, K, V, S = DefaultHashBuilder, A: Allocator = Global> {
+    /// The key exists in the map.
+    Occupied(OccupiedEntry<'a, K, V, S, A>),
+    /// The key doesn't exist in the map.
+    Vacant(VacantEntry<'a, K, V, S, A>),
+}
+
+impl<'a, K, V, S, A> Entry<'a, K, V, S, A>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Returns a reference to the key of this entry.
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Returns the index of this entry.
+    ///
+    /// If the entry is vacant, the returned index is the index it will have after insertion.
+    #[must_use]
+    #[inline]
+    pub fn index(&self) -> usize {
+        match self {
+            Entry::Occupied(entry) => entry.index(),
+            Entry::Vacant(entry) => entry.index(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant, and returns a mutable reference to the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// *map.entry("a").or_insert(1) += 10;
+    /// *map.entry("a").or_insert(1) += 10;
+    ///
+    /// assert_eq!(map.get(&"a"), Some(&21));
+    /// ```
+    #[inline]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Inserts the result of `call` if the entry is vacant, and returns a mutable reference to
+    /// the value.
+    ///
+    /// `call` is not called if the entry is occupied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map: OmniMap<&str, Vec<u8>> = OmniMap::new();
+    ///
+    /// map.entry("a").or_insert_with(Vec::new).push(1);
+    /// map.entry("a").or_insert_with(Vec::new).push(2);
+    ///
+    /// assert_eq!(map.get(&"a"), Some(&vec![1, 2]));
+    /// ```
+    #[inline]
+    pub fn or_insert_with<F>(self, call: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(call()),
+        }
+    }
+
+    /// Inserts the result of `call` with a reference to the key if the entry is vacant, and
+    /// returns a mutable reference to the value.
+    ///
+    /// `call` is not called if the entry is occupied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.entry("abc").or_insert_with_key(|key| key.len());
+    ///
+    /// assert_eq!(map.get(&"abc"), Some(&3));
+    /// ```
+    #[inline]
+    pub fn or_insert_with_key<F>(self, call: F) -> &'a mut V
+    where
+        F: FnOnce(&K) -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = call(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Inserts the default value of `V` if the entry is vacant, and returns a mutable reference
+    /// to the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map: OmniMap<&str, u32> = OmniMap::new();
+    ///
+    /// *map.entry("a").or_default() += 1;
+    ///
+    /// assert_eq!(map.get(&"a"), Some(&1));
+    /// ```
+    #[inline]
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(V::default()),
+        }
+    }
+
+    /// Calls `call` with a mutable reference to the value if the entry is occupied, and returns
+    /// the entry for further chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.entry("a").and_modify(|v| *v += 1).or_insert(1);
+    /// map.entry("a").and_modify(|v| *v += 1).or_insert(1);
+    ///
+    /// assert_eq!(map.get(&"a"), Some(&2));
+    /// ```
+    #[inline]
+    pub fn and_modify<F>(mut self, call: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let Entry::Occupied(entry) = &mut self {
+            call(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<K, V, S, A> Debug for Entry<'_, K, V, S, A>
+where
+    K: Eq + Hash + Debug,
+    V: Debug,
+    S: BuildHasher,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entry::Occupied(entry) => f.debug_tuple("Entry").field(entry).finish(),
+            Entry::Vacant(entry) => f.debug_tuple("Entry").field(entry).finish(),
+        }
+    }
+}
+
+/// A view into an occupied entry in the map.
+///
+/// It is a part of the [`Entry`] enum.
+pub struct OccupiedEntry<'a, K, V, S = DefaultHashBuilder, A: Allocator = Global> {
+    map: &'a mut OmniMap<K, V, S, A>,
+    slot: usize,
+    index: usize,
+}
+
+impl<'a, K, V, S, A> OccupiedEntry<'a, K, V, S, A>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Creates a new view of the entry at `index`, which is referenced by the occupied `slot`.
+    #[inline(always)]
+    pub(crate) fn new(map: &'a mut OmniMap<K, V, S, A>, slot: usize, index: usize) -> Self {
+        Self { map, slot, index }
+    }
+
+    /// Returns a reference to the key of this entry.
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &K {
+        unsafe { &self.map.bucket(self.index).key }
+    }
+
+    /// Returns the index of this entry.
+    #[must_use]
+    #[inline(always)]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns a reference to the value of this entry.
+    #[must_use]
+    #[inline]
+    pub fn get(&self) -> &V {
+        unsafe { &self.map.bucket(self.index).value }
+    }
+
+    /// Returns a mutable reference to the value of this entry.
+    ///
+    /// To get a reference that outlives the entry, use [`OccupiedEntry::into_mut`].
+    #[must_use]
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { &mut self.map.bucket_mut(self.index).value }
+    }
+
+    /// Converts the entry into a mutable reference to its value with the lifetime of the map.
+    #[must_use]
+    #[inline]
+    pub fn into_mut(self) -> &'a mut V {
+        unsafe { &mut self.map.bucket_mut(self.index).value }
+    }
+
+    /// Sets the value of this entry to `value`, and returns the old value.
+    #[inline]
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes this entry and returns its value.
+    ///
+    /// This method shifts all entries after it to fill the gap, like [`OmniMap::shift_remove`].
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n) on average.
+    #[inline]
+    pub fn shift_remove(self) -> V {
+        self.shift_remove_entry().1
+    }
+
+    /// Removes this entry and returns its key and value.
+    ///
+    /// This method shifts all entries after it to fill the gap, like [`OmniMap::shift_remove`].
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n) on average.
+    #[inline]
+    pub fn shift_remove_entry(self) -> (K, V) {
+        let removed = unsafe { self.map.remove_found::<true>(self.slot, self.index) };
+        (removed.key, removed.value)
+    }
+
+    /// Removes this entry and returns its value.
+    ///
+    /// The last entry takes the place of the removed entry, like [`OmniMap::swap_remove`].
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    #[inline]
+    pub fn swap_remove(self) -> V {
+        self.swap_remove_entry().1
+    }
+
+    /// Removes this entry and returns its key and value.
+    ///
+    /// The last entry takes the place of the removed entry, like [`OmniMap::swap_remove`].
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    #[inline]
+    pub fn swap_remove_entry(self) -> (K, V) {
+        let removed = unsafe { self.map.remove_found::<false>(self.slot, self.index) };
+        (removed.key, removed.value)
+    }
+}
+
+impl<K, V, S, A> Debug for OccupiedEntry<'_, K, V, S, A>
+where
+    K: Eq + Hash + Debug,
+    V: Debug,
+    S: BuildHasher,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OccupiedEntry")
+            .field("key", self.key())
+            .field("value", self.get())
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// A view into a vacant entry in the map.
+///
+/// It is a part of the [`Entry`] enum.
+pub struct VacantEntry<'a, K, V, S = DefaultHashBuilder, A: Allocator = Global> {
+    map: &'a mut OmniMap<K, V, S, A>,
+    key: K,
+    hash: usize,
+    slot: usize,
+}
+
+impl<'a, K, V, S, A> VacantEntry<'a, K, V, S, A>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Creates a new view of a vacant entry, which will take the free `slot` when inserted.
+    #[inline(always)]
+    pub(crate) fn new(map: &'a mut OmniMap<K, V, S, A>, key: K, hash: usize, slot: usize) -> Self {
+        Self {
+            map,
+            key,
+            hash,
+            slot,
+        }
+    }
+
+    /// Returns a reference to the key that would be used when inserting.
+    #[must_use]
+    #[inline(always)]
+    pub const fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes the ownership of the key.
+    #[must_use]
+    #[inline]
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Returns the index that the entry will have after insertion.
+    #[must_use]
+    #[inline(always)]
+    pub const fn index(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Inserts the entry with `value` at the end of the map, and returns a mutable reference to
+    /// the value.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    #[inline]
+    pub fn insert(self, value: V) -> &'a mut V {
+        unsafe {
+            // Capacity has been reserved when the entry was created.
+            let index = self
+                .map
+                .insert_unique(self.slot, self.hash, self.key, value);
+            &mut self.map.bucket_mut(index).value
+        }
+    }
+}
+
+impl<K, V, S, A> Debug for VacantEntry<'_, K, V, S, A>
+where
+    K: Debug,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VacantEntry").field(&self.key).finish()
+    }
+}
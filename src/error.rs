@@ -1,5 +1,5 @@
+use alloc_crate::alloc::handle_alloc_error;
 use core::alloc::Layout;
-use std::alloc::handle_alloc_error;
 
 #[derive(Clone, Copy, Debug)]
 pub enum AllocError {
@@ -0,0 +1,235 @@
+// The implementation of `Group` is selected at compile time. SSE2 compares 16 tags with a single
+// instruction, and the generic implementation compares 8 tags packed in a `u64` (SWAR).
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(miri)
+))]
+use sse2 as imp;
+
+#[cfg(not(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(miri)
+)))]
+use generic as imp;
+
+pub(crate) use imp::Group;
+
+/// A set of positions in a group, as returned by the matching methods of [`Group`].
+///
+/// Each position is represented by `STRIDE` bits, and only the highest of them may be set.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BitMask(imp::BitMaskWord);
+
+impl BitMask {
+    /// Returns the lowest position in the set, or `None` if the set is empty.
+    #[must_use]
+    #[inline(always)]
+    pub(crate) const fn lowest_set_bit(self) -> Option<usize> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(self.0.trailing_zeros() as usize / imp::STRIDE)
+        }
+    }
+
+    /// Returns the set without the positions greater than or equal to `len`.
+    ///
+    /// `len` must not exceed [`Group::WIDTH`].
+    #[must_use]
+    #[inline(always)]
+    pub(crate) const fn take(self, len: usize) -> Self {
+        if len >= Group::WIDTH {
+            self
+        } else {
+            BitMask(self.0 & ((1 << (len * imp::STRIDE)) - 1))
+        }
+    }
+}
+
+impl Iterator for BitMask {
+    type Item = usize;
+
+    /// Returns the lowest position in the set and removes it.
+    #[inline(always)]
+    fn next(&mut self) -> Option<usize> {
+        let bit = self.lowest_set_bit()?;
+        self.0 &= self.0 - 1;
+        Some(bit)
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(miri)
+))]
+mod sse2 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86 as arch;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64 as arch;
+
+    use super::BitMask;
+    use crate::index::Tag;
+
+    pub(crate) type BitMaskWord = u16;
+    pub(crate) const STRIDE: usize = 1;
+
+    /// A group of control tags that are compared at once.
+    #[derive(Clone, Copy)]
+    pub(crate) struct Group(arch::__m128i);
+
+    impl Group {
+        /// The count of the control tags in the group.
+        pub(crate) const WIDTH: usize = size_of::<Self>();
+
+        /// Loads a group of control tags starting at `ptr`.
+        ///
+        /// # Safety
+        ///
+        /// `ptr` must be valid for reads of [`Group::WIDTH`] bytes. No alignment is required.
+        #[inline(always)]
+        pub(crate) unsafe fn load(ptr: *const Tag) -> Self {
+            Group(arch::_mm_loadu_si128(ptr.cast()))
+        }
+
+        /// Returns the positions of the tags that are equal to `tag`.
+        #[inline(always)]
+        pub(crate) fn match_tag(self, tag: Tag) -> BitMask {
+            unsafe {
+                let cmp = arch::_mm_cmpeq_epi8(self.0, arch::_mm_set1_epi8(tag.0 as i8));
+                BitMask(arch::_mm_movemask_epi8(cmp) as u16)
+            }
+        }
+
+        /// Returns the positions of the empty tags.
+        #[inline(always)]
+        pub(crate) fn match_empty(self) -> BitMask {
+            // The high bit of a tag is set only if the slot is occupied.
+            unsafe { BitMask(!(arch::_mm_movemask_epi8(self.0) as u16)) }
+        }
+    }
+}
+
+#[cfg(not(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(miri)
+)))]
+mod generic {
+    use super::BitMask;
+    use crate::index::Tag;
+
+    pub(crate) type BitMaskWord = u64;
+    pub(crate) const STRIDE: usize = 8;
+
+    /// Returns a word that has `byte` in all of its bytes.
+    #[inline(always)]
+    const fn repeat(byte: u8) -> u64 {
+        u64::from_ne_bytes([byte; 8])
+    }
+
+    /// A group of control tags that are compared at once.
+    #[derive(Clone, Copy)]
+    pub(crate) struct Group(u64);
+
+    impl Group {
+        /// The count of the control tags in the group.
+        pub(crate) const WIDTH: usize = size_of::<Self>();
+
+        /// Loads a group of control tags starting at `ptr`.
+        ///
+        /// The tags are loaded in little-endian order, so the first tag is in the lowest byte on
+        /// all targets.
+        ///
+        /// # Safety
+        ///
+        /// `ptr` must be valid for reads of [`Group::WIDTH`] bytes. No alignment is required.
+        #[inline(always)]
+        pub(crate) unsafe fn load(ptr: *const Tag) -> Self {
+            Group(u64::from_le(ptr.cast::<u64>().read_unaligned()))
+        }
+
+        /// Returns the positions of the tags that are equal to `tag`.
+        ///
+        /// The result may contain false positives, but only at positions after a true match.
+        /// The caller must verify each match anyway, because different hashes can have the same
+        /// fingerprint.
+        #[inline(always)]
+        pub(crate) fn match_tag(self, tag: Tag) -> BitMask {
+            // The bytes that are equal to the tag become zero, and the high bit of each zero byte
+            // is set by the borrow of the subtraction.
+            let cmp = self.0 ^ repeat(tag.0);
+            BitMask(cmp.wrapping_sub(repeat(0x01)) & !cmp & repeat(0x80))
+        }
+
+        /// Returns the positions of the empty tags.
+        #[inline(always)]
+        pub(crate) fn match_empty(self) -> BitMask {
+            // The high bit of a tag is set only if the slot is occupied.
+            BitMask(!self.0 & repeat(0x80))
+        }
+    }
+}
+
+#[cfg(test)]
+mod group_tests {
+    use super::*;
+    use crate::index::Tag;
+
+    /// Returns the group of `tags`, padded with empty tags.
+    fn group_of(tags: &[Tag]) -> Group {
+        let mut bytes = [Tag::EMPTY; 16];
+        bytes[..tags.len()].copy_from_slice(tags);
+        unsafe { Group::load(bytes.as_ptr()) }
+    }
+
+    #[test]
+    fn test_group_match_empty() {
+        let full = Tag::full(usize::MAX);
+        let group = group_of(&[full, Tag::EMPTY, full, full, Tag::EMPTY]);
+
+        let empty: Vec<usize> = group.match_empty().take(5).collect();
+        assert_eq!(empty, vec![1, 4]);
+
+        assert_eq!(group.match_empty().lowest_set_bit(), Some(1));
+
+        // The padding is empty.
+        assert_eq!(group.match_empty().count(), Group::WIDTH - 3);
+    }
+
+    #[test]
+    fn test_group_match_tag() {
+        let a = Tag::full(0);
+        let b = Tag::full(usize::MAX);
+        let group = group_of(&[a, b, Tag::EMPTY, b, a]);
+
+        let matches: Vec<usize> = group.match_tag(b).collect();
+        assert_eq!(matches, vec![1, 3]);
+
+        // The tags of `a` are matched at least, and the false positives can only follow a match.
+        let matches: Vec<usize> = group.match_tag(a).collect();
+        assert!(matches.contains(&0) && matches.contains(&4));
+        assert!(!matches.contains(&2));
+    }
+
+    #[test]
+    fn test_group_match_tag_full_width() {
+        let tag = Tag::full(usize::MAX);
+        let group = group_of(&[tag; 16][..Group::WIDTH]);
+
+        assert_eq!(group.match_tag(tag).count(), Group::WIDTH);
+        assert_eq!(group.match_empty().lowest_set_bit(), None);
+    }
+
+    #[test]
+    fn test_bitmask_take() {
+        let group = group_of(&[]);
+
+        assert_eq!(group.match_empty().take(0).lowest_set_bit(), None);
+        assert_eq!(group.match_empty().take(3).count(), 3);
+        assert_eq!(group.match_empty().take(Group::WIDTH).count(), Group::WIDTH);
+    }
+}
@@ -0,0 +1,376 @@
+use core::fmt;
+use core::hash::{BuildHasher, Hasher};
+
+/// The default hash builder of [`OmniMap`](crate::OmniMap).
+pub type DefaultHashBuilder = RandomState;
+
+/// A hash builder that builds [`SipHasher13`] instances with a per-instance pair of keys.
+///
+/// Instances created with [`RandomState::new`] draw new random keys, so an attacker can't
+/// predict which keys collide in a map, which protects the linear probing of the index against
+/// hash flooding.
+///
+/// Instances created with [`RandomState::with_seed`] are deterministic, and the same seed
+/// produces the same hashes in every process and on every target, as long as the `Hash`
+/// implementations of the keys write the same data. This is useful for tests and reproducible
+/// snapshots. Note that the `Hash` implementations of the standard library are not guaranteed to
+/// remain the same between versions of Rust.
+///
+/// Two instances are equal when they have the same keys, and therefore produce the same hashes.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RandomState {
+    k0: u64,
+    k1: u64,
+}
+
+impl RandomState {
+    /// Creates a new `RandomState` with random keys.
+    ///
+    /// The randomness is taken from the standard library's `RandomState`, which is seeded by
+    /// the operating system once per thread and changes with every instance.
+    ///
+    /// This function requires the feature `std`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        let source = std::collections::hash_map::RandomState::new();
+        Self {
+            k0: source.hash_one(0_u64),
+            k1: source.hash_one(1_u64),
+        }
+    }
+
+    /// Creates a new `RandomState` with keys derived deterministically from `seed`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::hash::BuildHasher;
+    /// use omnimap::RandomState;
+    ///
+    /// let a = RandomState::with_seed(42);
+    /// let b = RandomState::with_seed(42);
+    ///
+    /// assert_eq!(a.hash_one("key"), b.hash_one("key"));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_seed(seed: u64) -> Self {
+        let k0 = split_mix(seed);
+        let k1 = split_mix(k0);
+        Self { k0, k1 }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for RandomState {
+    /// Creates a new `RandomState` with random keys.
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BuildHasher for RandomState {
+    type Hasher = SipHasher13;
+
+    #[inline]
+    fn build_hasher(&self) -> SipHasher13 {
+        SipHasher13::new_with_keys(self.k0, self.k1)
+    }
+}
+
+impl fmt::Debug for RandomState {
+    /// The keys are intentionally not printed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RandomState").finish_non_exhaustive()
+    }
+}
+
+/// Returns the next output of the `SplitMix64` generator for the state `x`.
+///
+/// It is used to spread the bits of a seed over the keys of the hasher.
+#[inline(always)]
+const fn split_mix(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// The internal state of SipHash.
+#[derive(Clone, Copy)]
+struct State {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+}
+
+impl State {
+    /// A single `SipRound`.
+    #[inline(always)]
+    const fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13);
+        self.v1 ^= self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16);
+        self.v3 ^= self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21);
+        self.v3 ^= self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17);
+        self.v1 ^= self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    /// Compresses one message word `m` with one round.
+    #[inline(always)]
+    const fn compress(&mut self, m: u64) {
+        self.v3 ^= m;
+        self.round();
+        self.v0 ^= m;
+    }
+}
+
+/// An implementation of SipHash 1-3, the same algorithm used by the standard library.
+///
+/// Unlike the standard library's hasher, the algorithm of this hasher is guaranteed to remain
+/// the same, so hashes are reproducible with the same keys.
+///
+/// Integers are written in little-endian byte order, and `usize` and `isize` are written as
+/// 64-bit integers, so the hashes don't depend on the endianness or the pointer width of the
+/// target.
+#[derive(Clone)]
+pub struct SipHasher13 {
+    state: State,
+    // The total number of bytes written.
+    length: usize,
+    // The unprocessed bytes in little-endian order.
+    tail: u64,
+    // The number of valid bytes in `tail`.
+    ntail: usize,
+}
+
+impl SipHasher13 {
+    /// Creates a new hasher with the keys `k0` and `k1`.
+    #[must_use]
+    #[inline]
+    pub const fn new_with_keys(k0: u64, k1: u64) -> Self {
+        Self {
+            state: State {
+                v0: k0 ^ 0x736f_6d65_7073_6575,
+                v1: k1 ^ 0x646f_7261_6e64_6f6d,
+                v2: k0 ^ 0x6c79_6765_6e65_7261,
+                v3: k1 ^ 0x7465_6462_7974_6573,
+            },
+            length: 0,
+            tail: 0,
+            ntail: 0,
+        }
+    }
+}
+
+/// Reads up to `8` bytes as a little-endian integer.
+#[inline(always)]
+const fn read_le(bytes: &[u8]) -> u64 {
+    debug_assert!(bytes.len() <= 8);
+    let mut out = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        out |= (bytes[i] as u64) << (8 * i);
+        i += 1;
+    }
+    out
+}
+
+impl Hasher for SipHasher13 {
+    #[inline]
+    fn write(&mut self, msg: &[u8]) {
+        let length = msg.len();
+        self.length += length;
+
+        let mut offset = 0;
+
+        // Complete the tail from the previous write first.
+        if self.ntail != 0 {
+            let needed = 8 - self.ntail;
+            let fill = if length < needed { length } else { needed };
+            self.tail |= read_le(&msg[..fill]) << (8 * self.ntail);
+            if length < needed {
+                self.ntail += length;
+                return;
+            }
+            self.state.compress(self.tail);
+            self.ntail = 0;
+            offset = needed;
+        }
+
+        let rest = &msg[offset..];
+        let mut words = rest.chunks_exact(8);
+
+        for word in &mut words {
+            self.state.compress(read_le(word));
+        }
+
+        let remainder = words.remainder();
+        self.tail = read_le(remainder);
+        self.ntail = remainder.len();
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        // Sign-extended, so that negative values are the same on all pointer widths.
+        self.write_u64(i as i64 as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        let mut state = self.state;
+
+        let last = (((self.length as u64) & 0xff) << 56) | self.tail;
+        state.compress(last);
+
+        state.v2 ^= 0xff;
+        state.round();
+        state.round();
+        state.round();
+
+        state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+    }
+}
+
+impl fmt::Debug for SipHasher13 {
+    /// The state is intentionally not printed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SipHasher13").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod hash_tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+
+    #[test]
+    fn test_sip_hasher_reference() {
+        // The standard library's `DefaultHasher` is SipHash 1-3 with zero keys.
+        let inputs: [&[u8]; 5] = [b"", b"a", b"abcdefg", b"abcdefgh", b"0123456789abcdefghij"];
+        for input in inputs {
+            let mut ours = SipHasher13::new_with_keys(0, 0);
+            let mut reference = DefaultHasher::new();
+            ours.write(input);
+            reference.write(input);
+            assert_eq!(ours.finish(), reference.finish());
+        }
+    }
+
+    #[test]
+    fn test_sip_hasher_split_writes() {
+        let msg = b"The quick brown fox jumps over the lazy dog";
+
+        let mut whole = SipHasher13::new_with_keys(1, 2);
+        whole.write(msg);
+
+        // Writing in arbitrary chunks must produce the same hash.
+        for chunk_size in 1..msg.len() {
+            let mut chunked = SipHasher13::new_with_keys(1, 2);
+            for chunk in msg.chunks(chunk_size) {
+                chunked.write(chunk);
+            }
+            assert_eq!(chunked.finish(), whole.finish());
+        }
+    }
+
+    #[test]
+    fn test_random_state_seeded() {
+        let a = RandomState::with_seed(7);
+        let b = RandomState::with_seed(7);
+        let c = RandomState::with_seed(8);
+
+        assert_eq!(a.hash_one("key"), b.hash_one("key"));
+        assert_ne!(a.hash_one("key"), c.hash_one("key"));
+
+        assert_eq!(a, b);
+        assert_eq!(a, a.clone());
+        assert_ne!(a, c);
+
+        // Reproducible across processes and targets.
+        let mut hasher = RandomState::with_seed(0).build_hasher();
+        hasher.write(b"omnimap");
+        assert_eq!(hasher.finish(), 0x0e9c_1984_ade2_d51c);
+
+        let mut hasher = RandomState::with_seed(0).build_hasher();
+        hasher.write_u32(0x0102_0304);
+        hasher.write_usize(7);
+        hasher.write_isize(-1);
+        assert_eq!(hasher.finish(), 0x7360_9126_e837_10aa);
+    }
+
+    #[test]
+    fn test_sip_hasher_integers() {
+        // Integers are written as little-endian bytes.
+        let mut a = SipHasher13::new_with_keys(1, 2);
+        let mut b = SipHasher13::new_with_keys(1, 2);
+        a.write_u16(0x0102);
+        a.write_u32(0x0304_0506);
+        a.write_u64(0x0708_090a_0b0c_0d0e);
+        a.write_u128(0x0f);
+        b.write(&[0x02, 0x01]);
+        b.write(&[0x06, 0x05, 0x04, 0x03]);
+        b.write(&[0x0e, 0x0d, 0x0c, 0x0b, 0x0a, 0x09, 0x08, 0x07]);
+        b.write(&[0x0f, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(a.finish(), b.finish());
+
+        // The pointer-sized integers are written as 64-bit integers.
+        let mut a = SipHasher13::new_with_keys(1, 2);
+        let mut b = SipHasher13::new_with_keys(1, 2);
+        a.write_usize(3);
+        a.write_isize(-3);
+        b.write_u64(3);
+        b.write_i64(-3);
+        assert_eq!(a.finish(), b.finish());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_random_state_random() {
+        let a = RandomState::new();
+        let b = RandomState::new();
+
+        // Every instance has its own keys.
+        assert_ne!(a.hash_one("key"), b.hash_one("key"));
+
+        // But hashing is consistent within the same instance.
+        assert_eq!(a.hash_one("key"), a.hash_one("key"));
+    }
+}
@@ -1,52 +1,67 @@
 use core::alloc::Layout;
 use core::hint::unreachable_unchecked;
 
-use crate::alloc::UnsafeBufferPointer;
+use crate::alloc::{Allocator, UnsafeBufferPointer};
 use crate::error::OnError;
+use crate::group::Group;
 use crate::AllocError;
 
-/// The state of the slot in the index.
-#[derive(Clone, Copy, Debug)]
-#[repr(u8)]
-pub(crate) enum Tag {
-    Empty = 0,
-    Deleted = 1,
-    Occupied = 2,
-}
+/// The control tag of a slot in the index.
+///
+/// The tag of an empty slot is zero. The tag of an occupied slot has the high bit set, and its
+/// low 7 bits are a fingerprint of the hash of the entry, so that most of the mismatches can be
+/// rejected while probing without loading the entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub(crate) struct Tag(pub(crate) u8);
 
 impl Tag {
+    /// The tag of an empty slot.
+    pub(crate) const EMPTY: Tag = Tag(0);
+
+    /// Returns the tag of an occupied slot for an entry with the specified `hash`.
     #[inline(always)]
-    pub(crate) const fn is_empty(self) -> bool {
-        self as u8 == Tag::Empty as u8
+    pub(crate) const fn full(hash: usize) -> Tag {
+        // The fingerprint is taken from the top bits, because the ideal slot depends mostly on
+        // the low bits.
+        Tag(0x80 | (hash >> (usize::BITS - 7)) as u8)
     }
 
+    /// Checks if the tag is equal to `other`, in constant functions.
     #[inline(always)]
-    pub(crate) const fn is_deleted(self) -> bool {
-        self as u8 == Tag::Deleted as u8
+    pub(crate) const fn is(self, other: Tag) -> bool {
+        self.0 == other.0
+    }
+
+    #[inline(always)]
+    pub(crate) const fn is_empty(self) -> bool {
+        self.0 == Tag::EMPTY.0
     }
 
     #[inline(always)]
     pub(crate) const fn is_occupied(self) -> bool {
-        self as u8 == Tag::Occupied as u8
+        self.0 & 0x80 != 0
     }
 }
 
 /// A helper type to manage index's memory.
 pub(crate) struct MapIndex {
     // Figure 1:
-    // -----------------------------------------------------------------------------------
-    // |                        The memory layout of the index                           |
-    // | |------------ usize * N ------------|---------- u8 * N ------------|- u8 * X -| |
-    // | [ IndexN-1 | ... | Index1 | Index0 ]|[ Tag0 | Tag1 | ... | TagN-1 ] [ Padding ] |
-    // |                usize strides (-) <- ^ -> (+) u8 strides                         |
-    // |                                     |                                           |
-    // |                                  Pointer                                        |
-    // | Legend:                                                                         |
-    // | N: The allocated capacity.                                                      |
-    // | X: The required bytes to round the total size to multiple of usize's alignment. |
-    // | Tag: A single byte as flag to store slot's state.                               |
-    // | Index: A usize-value that stores an offset where an entry can be located.       |
-    // -----------------------------------------------------------------------------------
+    // -----------------------------------------------------------------------------------------
+    // |                           The memory layout of the index                              |
+    // | |------------ usize * N ------------|------- u8 * N -------|-- u8 * W --|- u8 * X -| |
+    // | [ IndexN-1 | ... | Index1 | Index0 ]|[ Tag0 | ... | TagN-1 ][ Mirror    ][ Padding ] |
+    // |                usize strides (-) <- ^ -> (+) u8 strides                               |
+    // |                                     |                                                 |
+    // |                                  Pointer                                              |
+    // | Legend:                                                                               |
+    // | N: The allocated capacity.                                                            |
+    // | W: The width of a group of tags.                                                      |
+    // | X: The required bytes to round the total size to multiple of usize's alignment.       |
+    // | Tag: A single byte that stores slot's state and the fingerprint of entry's hash.      |
+    // | Mirror: A copy of the first W tags, so that a group can be loaded at any slot.        |
+    // | Index: A usize-value that stores an offset where an entry can be located.             |
+    // -----------------------------------------------------------------------------------------
     pointer: UnsafeBufferPointer<u8>,
 }
 
@@ -55,6 +70,13 @@ impl MapIndex {
     const T_ALIGN: usize = align_of::<usize>();
     const T_MAX_ALLOC_SIZE: usize = (isize::MAX as usize + 1) - Self::T_ALIGN;
 
+    /// Returns the count of the control tags of the index for a given capacity `cap`, including
+    /// the mirrored tags.
+    #[inline(always)]
+    const fn tags_count(cap: usize) -> usize {
+        cap + Group::WIDTH
+    }
+
     /// Returns the `(aligned layout, slots size)` of the index for a given capacity `cap`.
     /// Size and alignment are calculated for `usize`.
     ///
@@ -62,7 +84,8 @@ impl MapIndex {
     #[inline]
     fn index_layout(cap: usize) -> Option<(Layout, usize)> {
         let slots_size = cap.checked_mul(Self::T_SIZE)?;
-        let aligned_tags = (cap + Self::T_ALIGN - 1) & !(Self::T_ALIGN - 1);
+        let tags = Self::tags_count(cap).checked_add(Self::T_ALIGN - 1)?;
+        let aligned_tags = tags & !(Self::T_ALIGN - 1);
         let total_size = slots_size.checked_add(aligned_tags)?;
         if Self::T_MAX_ALLOC_SIZE > total_size {
             let layout = unsafe { Layout::from_size_align_unchecked(total_size, Self::T_ALIGN) };
@@ -85,19 +108,20 @@ impl MapIndex {
         }
     }
 
-    /// Creates new instance and allocates memory according to the capacity `cap`, without
-    /// initializing control tags.
+    /// Creates new instance and allocates memory with the allocator `alloc` according to the
+    /// capacity `cap`, without initializing control tags.
     ///
     /// Handling of errors will be done according to the error handling context `on_err`.
     #[inline]
-    pub(crate) unsafe fn new_allocate_uninit(
+    pub(crate) unsafe fn new_allocate_uninit<A: Allocator>(
         cap: usize,
+        alloc: &A,
         on_err: OnError,
     ) -> Result<Self, AllocError> {
         match Self::index_layout(cap) {
             Some((layout, slots_size)) => {
                 let mut pointer = UnsafeBufferPointer::new();
-                pointer.allocate(layout, on_err)?;
+                pointer.allocate(alloc, layout, on_err)?;
                 // Set the pointer at the offset of the control tags.
                 pointer.set_plus(slots_size);
                 Ok(Self { pointer })
@@ -116,7 +140,7 @@ impl MapIndex {
     pub(crate) const unsafe fn copy_from(&mut self, source: &MapIndex, cap: usize) {
         let slots_size = cap * Self::T_SIZE;
         // Copy the useful data without the padding bytes.
-        let unaligned_size = slots_size + cap;
+        let unaligned_size = slots_size + Self::tags_count(cap);
 
         let source_start = source.pointer.access().sub(slots_size);
         let self_start = self.pointer.access().sub(slots_size);
@@ -131,13 +155,14 @@ impl MapIndex {
     ///
     /// - Index must be allocated before calling this method.
     /// - `cap` must be the same allocated capacity.
+    /// - `alloc` must be the same allocator used to allocate the index.
     #[inline]
-    pub(crate) unsafe fn deallocate(&mut self, cap: usize) {
+    pub(crate) unsafe fn deallocate<A: Allocator>(&mut self, alloc: &A, cap: usize) {
         match Self::index_layout(cap) {
             Some((layout, slots_size)) => {
                 // Reset the pointer to the start of the allocated memory.
                 self.pointer.set_minus(slots_size);
-                self.pointer.deallocate(layout)
+                self.pointer.deallocate(alloc, layout)
             }
             // Already checked when allocated, so it must not fail.
             None => unreachable_unchecked(),
@@ -148,36 +173,40 @@ impl MapIndex {
     ///
     /// # Safety
     ///
-    /// - Index must be allocated and control tags must be initialized before calling this method.
-    ///
-    /// - Safe casting to `Tag` depends on initializing the control tags when allocating and
-    ///   reallocating and using `Tag` enum to store tag's value.
+    /// Index must be allocated and control tags must be initialized before calling this method.
     #[inline(always)]
     pub(crate) const unsafe fn read_tag(&self, offset: usize) -> Tag {
         self.pointer.access_as::<Tag>().add(offset).read()
     }
 
-    /// Stores the control tag at the specified tag's `offset`.
+    /// Loads the group of control tags starting at tag's `offset`.
+    ///
+    /// The group may extend past the last tag, in which case it continues with the mirrored tags
+    /// from the start of the index.
     ///
     /// # Safety
     ///
-    /// Index must be allocated before calling this method.
+    /// - Index must be allocated and control tags must be initialized before calling this method.
+    ///
+    /// - `offset` must be less than the allocated capacity.
     #[inline(always)]
-    pub(crate) const unsafe fn store_tag(&mut self, offset: usize, tag: Tag) {
-        self.pointer.store(offset, tag as u8);
+    pub(crate) unsafe fn load_group(&self, offset: usize) -> Group {
+        Group::load(self.pointer.access_as::<Tag>().add(offset))
     }
 
-    /// Returns a mutable reference to the control tag in the index at tag's `offset`.
+    /// Stores the control tag at the specified tag's `offset`, and its mirror if it has one.
     ///
     /// # Safety
     ///
-    /// - Index must be allocated and control tags must be initialized before calling this method.
+    /// - Index must be allocated before calling this method.
     ///
-    /// - Safe casting to `Tag` depends on initializing the control tags when allocating and
-    ///   reallocating and using `Tag` enum to store tag's value.
+    /// - `cap` must be the allocated capacity, and `offset` must be less than it.
     #[inline(always)]
-    pub(crate) const unsafe fn tag_ref_mut(&mut self, offset: usize) -> &mut Tag {
-        &mut *self.pointer.access_mut_as::<Tag>().add(offset)
+    pub(crate) const unsafe fn store_tag(&mut self, offset: usize, tag: Tag, cap: usize) {
+        self.pointer.store(offset, tag.0);
+        if offset < Group::WIDTH {
+            self.pointer.store(cap + offset, tag.0);
+        }
     }
 
     /// Reads and returns the slot's value according to the specified tag's `offset`.
@@ -217,27 +246,32 @@ impl MapIndex {
     ///
     /// # Safety
     ///
-    /// Index must be allocated before calling this method.
+    /// - Index must be allocated before calling this method.
+    ///
+    /// - `cap` must be the allocated capacity, and `offset` must be less than it.
     #[inline(always)]
-    pub(crate) const unsafe fn store(&mut self, offset: usize, tag: Tag, value: usize) {
-        self.store_tag(offset, tag);
+    pub(crate) const unsafe fn store(&mut self, offset: usize, tag: Tag, value: usize, cap: usize) {
+        self.store_tag(offset, tag, cap);
         self.store_entry_index(offset, value);
     }
 
-    /// Sets all control tags to empty.
+    /// Sets all control tags to empty, including the mirrored tags.
     ///
     /// # Safety
     ///
-    /// Index must be allocated before calling this method.
+    /// - Index must be allocated before calling this method.
+    ///
+    /// - `cap` must be the allocated capacity.
     #[inline(always)]
     pub(crate) const unsafe fn set_tags_empty(&mut self, cap: usize) {
-        self.pointer.memset_zero(cap)
+        self.pointer.memset_zero(Self::tags_count(cap))
     }
 }
 
 #[cfg(test)]
 mod index_tests {
     use super::*;
+    use crate::alloc::Global;
     use crate::defer;
 
     #[test]
@@ -248,8 +282,9 @@ mod index_tests {
 
         assert_eq!(slots_size, 80);
 
-        // 80 bytes for slots and 10 control tags and 6 padding bytes.
-        assert_eq!(layout.size(), 96);
+        // 80 bytes for slots, 10 control tags, the mirrored tags and the padding bytes.
+        let tags = (10 + Group::WIDTH).next_multiple_of(8);
+        assert_eq!(layout.size(), 80 + tags);
     }
 
     #[test]
@@ -261,18 +296,20 @@ mod index_tests {
     #[test]
     fn test_index_new_allocate_uninitialized() {
         unsafe {
-            let mut instance = MapIndex::new_allocate_uninit(10, OnError::NoReturn).unwrap();
+            let mut instance =
+                MapIndex::new_allocate_uninit(10, &Global, OnError::NoReturn).unwrap();
 
             assert!(!instance.pointer.is_null());
 
-            instance.deallocate(10)
+            instance.deallocate(&Global, 10)
         }
     }
 
     #[test]
     fn test_index_allocate_uninitialized_error() {
         unsafe {
-            let result = MapIndex::new_allocate_uninit(isize::MAX as usize, OnError::ReturnErr);
+            let result =
+                MapIndex::new_allocate_uninit(isize::MAX as usize, &Global, OnError::ReturnErr);
             assert!(result.is_err());
         }
     }
@@ -280,7 +317,8 @@ mod index_tests {
     #[test]
     fn test_index_store_read_tags() {
         unsafe {
-            let mut instance = MapIndex::new_allocate_uninit(10, OnError::NoReturn).unwrap();
+            let mut instance =
+                MapIndex::new_allocate_uninit(10, &Global, OnError::NoReturn).unwrap();
 
             instance.set_tags_empty(10);
 
@@ -289,21 +327,51 @@ mod index_tests {
             }
 
             for i in 0..10 {
-                instance.store_tag(i, Tag::Occupied)
+                instance.store_tag(i, Tag::full(i), 10)
             }
 
             for i in 0..10 {
                 assert!(instance.read_tag(i).is_occupied());
             }
 
-            instance.deallocate(10)
+            instance.deallocate(&Global, 10)
+        }
+    }
+
+    #[test]
+    fn test_index_mirrored_tags() {
+        unsafe {
+            // Smaller than any group, so the whole index is mirrored.
+            let cap = 3;
+            let mut instance =
+                MapIndex::new_allocate_uninit(cap, &Global, OnError::NoReturn).unwrap();
+
+            instance.set_tags_empty(cap);
+
+            let tag = Tag::full(usize::MAX);
+            instance.store_tag(0, tag, cap);
+            instance.store_tag(2, tag, cap);
+
+            assert_eq!(instance.read_tag(cap), tag);
+            assert!(instance.read_tag(cap + 1).is_empty());
+            assert_eq!(instance.read_tag(cap + 2), tag);
+
+            // A group loaded at the last slot wraps around to the first slots.
+            let matches: Vec<usize> = instance.load_group(2).match_tag(tag).take(cap).collect();
+            assert_eq!(matches, vec![0, 1]);
+
+            instance.store_tag(0, Tag::EMPTY, cap);
+            assert!(instance.read_tag(cap).is_empty());
+
+            instance.deallocate(&Global, cap)
         }
     }
 
     #[test]
     fn test_index_store_read_entry_index() {
         unsafe {
-            let mut instance = MapIndex::new_allocate_uninit(10, OnError::NoReturn).unwrap();
+            let mut instance =
+                MapIndex::new_allocate_uninit(10, &Global, OnError::NoReturn).unwrap();
 
             instance.set_tags_empty(10);
 
@@ -315,26 +383,26 @@ mod index_tests {
                 assert_eq!(instance.read_entry_index(i), 11);
             }
 
-            instance.deallocate(10)
+            instance.deallocate(&Global, 10)
         }
     }
 
     #[test]
     fn test_index_initialize_from() {
         unsafe {
-            let mut source = MapIndex::new_allocate_uninit(10, OnError::NoReturn).unwrap();
+            let mut source = MapIndex::new_allocate_uninit(10, &Global, OnError::NoReturn).unwrap();
 
             source.set_tags_empty(10);
 
             for i in 0..10 {
-                source.store_tag(i, Tag::Occupied)
+                source.store_tag(i, Tag::full(i), 10)
             }
 
             for i in 0..10 {
                 source.store_entry_index(i, 11)
             }
 
-            let mut target = MapIndex::new_allocate_uninit(10, OnError::NoReturn).unwrap();
+            let mut target = MapIndex::new_allocate_uninit(10, &Global, OnError::NoReturn).unwrap();
 
             target.copy_from(&source, 10);
 
@@ -346,20 +414,21 @@ mod index_tests {
                 assert_eq!(target.read_entry_index(i), 11);
             }
 
-            source.deallocate(10);
-            target.deallocate(10)
+            source.deallocate(&Global, 10);
+            target.deallocate(&Global, 10)
         }
     }
 
     #[test]
     fn test_index_reset_control_tags() {
         unsafe {
-            let mut instance = MapIndex::new_allocate_uninit(10, OnError::NoReturn).unwrap();
+            let mut instance =
+                MapIndex::new_allocate_uninit(10, &Global, OnError::NoReturn).unwrap();
 
             instance.set_tags_empty(10);
 
             for i in 0..10 {
-                instance.store_tag(i, Tag::Occupied)
+                instance.store_tag(i, Tag::full(i), 10)
             }
 
             instance.set_tags_empty(10);
@@ -368,7 +437,7 @@ mod index_tests {
                 assert!(instance.read_tag(i).is_empty());
             }
 
-            instance.deallocate(10);
+            instance.deallocate(&Global, 10);
         }
     }
 
@@ -376,11 +445,12 @@ mod index_tests {
     fn test_index_scope_guard() {
         unsafe {
             let cap = 10;
-            let mut instance = MapIndex::new_allocate_uninit(cap, OnError::NoReturn).unwrap();
+            let mut instance =
+                MapIndex::new_allocate_uninit(cap, &Global, OnError::NoReturn).unwrap();
             assert!(!instance.pointer.is_null());
 
             {
-                let _ = defer!(cap, instance.deallocate(*cap));
+                let _ = defer!(cap, instance.deallocate(&Global, *cap));
                 // Out of scope, dropped.
             }
 
@@ -393,18 +463,19 @@ mod index_tests {
     fn test_index_scope_guard_deactivate() {
         unsafe {
             let cap = 10;
-            let mut instance = MapIndex::new_allocate_uninit(cap, OnError::NoReturn).unwrap();
+            let mut instance =
+                MapIndex::new_allocate_uninit(cap, &Global, OnError::NoReturn).unwrap();
             assert!(!instance.pointer.is_null());
 
             {
-                let guard = defer!(cap, instance.deallocate(*cap));
+                let guard = defer!(cap, instance.deallocate(&Global, *cap));
                 guard.deactivate();
                 // Out of scope.
             }
 
             // Still allocated.
             assert!(!instance.pointer.is_null());
-            instance.deallocate(10);
+            instance.deallocate(&Global, 10);
         }
     }
 }
@@ -0,0 +1,230 @@
+use core::fmt;
+use core::fmt::Debug;
+use core::iter::FusedIterator;
+use core::slice;
+
+use crate::alloc::{Allocator, Global};
+use crate::map::{Bucket, OmniMapIterator};
+
+/// An iterator over the entries of a map or a slice.
+///
+/// This struct is created by [`OmniMap::iter`](crate::OmniMap::iter) and
+/// [`Slice::iter`](crate::Slice::iter).
+pub struct Iter<'a, K, V> {
+    inner: slice::Iter<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    #[inline(always)]
+    pub(crate) fn new(entries: &'a [Bucket<K, V>]) -> Self {
+        Self {
+            inner: entries.iter(),
+        }
+    }
+}
+
+/// An iterator over the entries of a map or a slice with mutable references to the values.
+///
+/// This struct is created by [`OmniMap::iter_mut`](crate::OmniMap::iter_mut) and
+/// [`Slice::iter_mut`](crate::Slice::iter_mut).
+pub struct IterMut<'a, K, V> {
+    inner: slice::IterMut<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> IterMut<'a, K, V> {
+    #[inline(always)]
+    pub(crate) fn new(entries: &'a mut [Bucket<K, V>]) -> Self {
+        Self {
+            inner: entries.iter_mut(),
+        }
+    }
+}
+
+/// An iterator over the keys of a map or a slice.
+///
+/// This struct is created by [`OmniMap::iter_keys`](crate::OmniMap::iter_keys) and
+/// [`Slice::keys`](crate::Slice::keys).
+pub struct Keys<'a, K, V> {
+    inner: slice::Iter<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> Keys<'a, K, V> {
+    #[inline(always)]
+    pub(crate) fn new(entries: &'a [Bucket<K, V>]) -> Self {
+        Self {
+            inner: entries.iter(),
+        }
+    }
+}
+
+/// An iterator over the values of a map or a slice.
+///
+/// This struct is created by [`OmniMap::iter_values`](crate::OmniMap::iter_values) and
+/// [`Slice::values`](crate::Slice::values).
+pub struct Values<'a, K, V> {
+    inner: slice::Iter<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> Values<'a, K, V> {
+    #[inline(always)]
+    pub(crate) fn new(entries: &'a [Bucket<K, V>]) -> Self {
+        Self {
+            inner: entries.iter(),
+        }
+    }
+}
+
+/// An iterator over mutable references to the values of a map or a slice.
+///
+/// This struct is created by [`OmniMap::values_mut`](crate::OmniMap::values_mut) and
+/// [`Slice::values_mut`](crate::Slice::values_mut).
+pub struct ValuesMut<'a, K, V> {
+    inner: slice::IterMut<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> ValuesMut<'a, K, V> {
+    #[inline(always)]
+    pub(crate) fn new(entries: &'a mut [Bucket<K, V>]) -> Self {
+        Self {
+            inner: entries.iter_mut(),
+        }
+    }
+}
+
+/// An owning iterator over the keys of a map.
+///
+/// This struct is created by [`OmniMap::into_keys`](crate::OmniMap::into_keys).
+pub struct IntoKeys<K, V, A: Allocator = Global> {
+    inner: OmniMapIterator<K, V, A>,
+}
+
+impl<K, V, A: Allocator> IntoKeys<K, V, A> {
+    #[inline(always)]
+    pub(crate) fn new(inner: OmniMapIterator<K, V, A>) -> Self {
+        Self { inner }
+    }
+}
+
+/// An owning iterator over the values of a map.
+///
+/// This struct is created by [`OmniMap::into_values`](crate::OmniMap::into_values).
+pub struct IntoValues<K, V, A: Allocator = Global> {
+    inner: OmniMapIterator<K, V, A>,
+}
+
+impl<K, V, A: Allocator> IntoValues<K, V, A> {
+    #[inline(always)]
+    pub(crate) fn new(inner: OmniMapIterator<K, V, A>) -> Self {
+        Self { inner }
+    }
+}
+
+// The iterators are thin adapters over an inner double-ended and exact-size iterator, so all the
+// methods forward to the inner iterator and map the yielded item.
+macro_rules! impl_iterator {
+    ([$($generics:tt)*] $ty:ty, $item:ty, |$entry:ident| $map:expr) => {
+        impl<$($generics)*> Iterator for $ty {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                self.inner.next().map(|$entry| $map)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.inner.size_hint()
+            }
+
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                self.inner.nth(n).map(|$entry| $map)
+            }
+
+            #[inline]
+            fn last(self) -> Option<Self::Item> {
+                self.inner.last().map(|$entry| $map)
+            }
+
+            #[inline]
+            fn count(self) -> usize {
+                self.inner.len()
+            }
+        }
+
+        impl<$($generics)*> DoubleEndedIterator for $ty {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.inner.next_back().map(|$entry| $map)
+            }
+
+            #[inline]
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                self.inner.nth_back(n).map(|$entry| $map)
+            }
+        }
+
+        impl<$($generics)*> ExactSizeIterator for $ty {
+            /// Returns the number of remaining items in the iterator.
+            #[inline(always)]
+            fn len(&self) -> usize {
+                self.inner.len()
+            }
+        }
+
+        impl<$($generics)*> FusedIterator for $ty {}
+    };
+}
+
+impl_iterator!(['a, K, V] Iter<'a, K, V>, (&'a K, &'a V), |entry| (&entry.key, &entry.value));
+impl_iterator!(['a, K, V] IterMut<'a, K, V>, (&'a K, &'a mut V), |entry| (&entry.key, &mut entry.value));
+impl_iterator!(['a, K, V] Keys<'a, K, V>, &'a K, |entry| &entry.key);
+impl_iterator!(['a, K, V] Values<'a, K, V>, &'a V, |entry| &entry.value);
+impl_iterator!(['a, K, V] ValuesMut<'a, K, V>, &'a mut V, |entry| &mut entry.value);
+impl_iterator!([K, V, A: Allocator] IntoKeys<K, V, A>, K, |entry| entry.0);
+impl_iterator!([K, V, A: Allocator] IntoValues<K, V, A>, V, |entry| entry.1);
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, V> Clone for Keys<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, V> Clone for Values<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for Iter<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<K: Debug, V> Debug for Keys<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<K, V: Debug> Debug for Values<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
@@ -1,3 +1,7 @@
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+extern crate alloc as alloc_crate;
 extern crate core;
 
 mod alloc;
@@ -6,10 +10,34 @@ mod map;
 mod tests;
 #[macro_use]
 mod builder;
+mod entry;
 mod error;
+mod group;
+mod hash;
 mod index;
+mod iter;
 mod opt;
+mod ordered;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "serde")]
+mod serde;
+mod set;
+mod slice;
 
 // Public exports.
+#[cfg(feature = "rayon")]
+pub use crate::rayon::{IntoParIter, ParIter, ParIterMut, ParKeys, ParValues};
+#[cfg(feature = "serde")]
+pub use crate::serde::DuplicateKeys;
+pub use alloc::{Allocator, Global};
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
 pub use error::AllocError;
-pub use map::{Entry, OmniMap, OmniMapIterator};
+pub use hash::{DefaultHashBuilder, RandomState, SipHasher13};
+pub use iter::{IntoKeys, IntoValues, Iter, IterMut, Keys, Values, ValuesMut};
+pub use map::{Drain, ExtractIf, OmniMap, OmniMapIterator};
+pub use ordered::OrderedMap;
+pub use set::{
+    Difference, Intersection, OmniSet, OmniSetIterator, SetIter, SymmetricDifference, Union,
+};
+pub use slice::Slice;
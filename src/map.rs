@@ -1,19 +1,25 @@
+use core::any::Any;
+use core::array;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
 use core::fmt::{Debug, Display};
-use core::hash::{Hash, Hasher};
+use core::hash::{BuildHasher, Hash};
 use core::hint::unreachable_unchecked;
-use core::iter::Map;
+use core::iter::FusedIterator;
 use core::mem::ManuallyDrop;
-use core::ops::{Index, IndexMut};
-use core::slice::{Iter, IterMut};
-use core::{fmt, mem};
+use core::ops::{Bound, Index, IndexMut, Range, RangeBounds};
+use core::{fmt, mem, ptr};
 
-use std::collections::hash_map::DefaultHasher;
-
-use crate::alloc::UnsafeBufferPointer;
+use crate::alloc::{Allocator, Global, UnsafeBufferPointer};
 use crate::defer;
+use crate::entry::{Entry, OccupiedEntry, VacantEntry};
 use crate::error::{AllocError, OnError};
+use crate::group::Group;
+use crate::hash::{DefaultHashBuilder, RandomState};
 use crate::index::{MapIndex, Tag};
+use crate::iter::{IntoKeys, IntoValues, Iter, IterMut, Keys, Values, ValuesMut};
 use crate::opt::branch_prediction::{likely, unlikely};
+use crate::slice::Slice;
 
 struct FindResult {
     slot: usize,
@@ -36,20 +42,21 @@ impl FindResult {
     }
 }
 
-pub struct Entry<K, V> {
-    key: K,
-    value: V,
-    hash: usize,
+/// The storage record of an entry in the map.
+pub struct Bucket<K, V> {
+    pub(crate) key: K,
+    pub(crate) value: V,
+    pub(crate) hash: usize,
 }
 
-impl<K, V> Entry<K, V> {
+impl<K, V> Bucket<K, V> {
     #[inline(always)]
     const fn new(key: K, value: V, hash: usize) -> Self {
         Self { key, value, hash }
     }
 }
 
-impl<K, V> Clone for Entry<K, V>
+impl<K, V> Clone for Bucket<K, V>
 where
     K: Clone,
     V: Clone,
@@ -63,13 +70,13 @@ where
     }
 }
 
-impl<K, V> Debug for Entry<K, V>
+impl<K, V> Debug for Bucket<K, V>
 where
     K: Debug,
     V: Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Entry")
+        f.debug_struct("Bucket")
             .field("key", &self.key)
             .field("value", &self.value)
             .field("hash", &self.hash)
@@ -77,37 +84,166 @@ where
     }
 }
 
-/// An immutable iterable view of the entries in the map.
-pub type EntriesIterator<'a, K, V> = Map<Iter<'a, Entry<K, V>>, fn(&Entry<K, V>) -> (&K, &V)>;
-
-/// A mutable iterable view of the entries in the map.
-///
-/// The keys are immutable, only the values can be modified.
-pub type EntriesIteratorMut<'a, K, V> =
-Map<IterMut<'a, Entry<K, V>>, fn(&mut Entry<K, V>) -> (&K, &mut V)>;
-
 /// A key-value data structure with hash-based indexing and ordered storage of entries, providing
 /// fast insertion, deletion, and retrieval of entries.
 ///
 /// It offers intuitive and ergonomic APIs inspired by hash maps and vectors, with the added
 /// benefit of predictable iteration order and stable indices.
-pub struct OmniMap<K, V> {
-    entries: UnsafeBufferPointer<Entry<K, V>>,
+///
+/// The hashing algorithm can be replaced on a per-instance basis by providing a different
+/// [`BuildHasher`] as `S`, using [`OmniMap::with_hasher`] or
+/// [`OmniMap::with_capacity_and_hasher`].
+///
+/// The memory of the entries and the index is allocated by the [`Allocator`] `A`, which can be
+/// provided on a per-instance basis using [`OmniMap::new_in`] or [`OmniMap::with_hasher_in`].
+pub struct OmniMap<K, V, S = DefaultHashBuilder, A: Allocator = Global> {
+    entries: UnsafeBufferPointer<Bucket<K, V>>,
     index: MapIndex,
     cap: usize,
     len: usize,
-    deleted: usize,
+    hash_builder: S,
+    alloc: A,
 }
 
-// Core implementation
-impl<K, V> OmniMap<K, V>
+// The map owns its entries and the index exclusively, the raw pointers are never shared with
+// other instances, and no method mutates the storage through a shared reference.
+unsafe impl<K, V, S, A> Send for OmniMap<K, V, S, A>
 where
-    K: Eq + Hash,
+    K: Send,
+    V: Send,
+    S: Send,
+    A: Allocator + Send,
 {
-    const DEFAULT_CAPACITY: usize = 16;
+}
+
+unsafe impl<K, V, S, A> Sync for OmniMap<K, V, S, A>
+where
+    K: Sync,
+    V: Sync,
+    S: Sync,
+    A: Allocator + Sync,
+{
+}
+
+/// A guard that rebuilds the index of the map when dropped.
+///
+/// It is used by the operations that permute the entries with user-provided functions, so the
+/// index remains consistent with the entries even if the function panics.
+struct ReindexGuard<'a, K, V, S, A: Allocator> {
+    map: &'a mut OmniMap<K, V, S, A>,
+}
+
+impl<K, V, S, A: Allocator> Drop for ReindexGuard<'_, K, V, S, A> {
+    #[inline]
+    fn drop(&mut self) {
+        self.map.reindex();
+    }
+}
+
+/// A guard that compacts the entries of the map while retaining.
+///
+/// When dropped, it moves the unprocessed entries to close the gap left by the removed entries,
+/// sets the length and rebuilds the index. This keeps the map consistent even if the predicate
+/// or the destructor of a removed entry panics.
+struct RetainGuard<'a, K, V, S, A: Allocator> {
+    map: &'a mut OmniMap<K, V, S, A>,
+    processed: usize,
+    removed: usize,
+    original_len: usize,
+}
+
+impl<K, V, S, A: Allocator> Drop for RetainGuard<'_, K, V, S, A> {
+    fn drop(&mut self) {
+        if self.removed == 0 {
+            // Nothing moved, the index is still valid.
+            self.map.len = self.original_len;
+            return;
+        }
+
+        unsafe {
+            // No-op if all entries have been processed.
+            self.map.entries.memmove(
+                self.processed,
+                self.processed - self.removed,
+                self.original_len - self.processed,
+            );
+        }
+
+        self.map.len = self.original_len - self.removed;
+        self.map.reindex();
+    }
+}
+
+/// Converts `range` to a range of indices within `len`.
+///
+/// Returns `None` if the start of the range is greater than its end, or if the end of the range
+/// is greater than `len`.
+pub(crate) fn try_bounds_of<R: RangeBounds<usize>>(range: R, len: usize) -> Option<Range<usize>> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    if start <= end && end <= len {
+        Some(start..end)
+    } else {
+        None
+    }
+}
+
+/// Converts `range` to a range of indices within `len`.
+///
+/// # Panics
+///
+/// Panics if the start of the range is greater than its end, or if the end of the range is
+/// greater than `len`.
+fn bounds_of<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    match try_bounds_of(range, len) {
+        Some(range) => range,
+        None => panic!("Index out of bounds."),
+    }
+}
+
+/// A guard that closes the gap left by the drained entries when dropped.
+///
+/// It moves the tail to the start of the drained range, restores the length and adds the tail to
+/// the index. This keeps the map consistent even if the destructor of a drained entry panics.
+struct DrainTailGuard<'r, 'a, K, V, S, A: Allocator> {
+    drain: &'r mut Drain<'a, K, V, S, A>,
+}
+
+impl<K, V, S, A: Allocator> Drop for DrainTailGuard<'_, '_, K, V, S, A> {
+    fn drop(&mut self) {
+        let drain = &mut *self.drain;
+
+        unsafe {
+            // No-op if the tail is empty.
+            drain
+                .map
+                .entries
+                .memmove(drain.tail, drain.start, drain.tail_len);
+        }
+
+        drain.map.len = drain.start + drain.tail_len;
+
+        // The entries before the start are still indexed.
+        drain.map.build_index_from(drain.start);
+    }
+}
 
+// Constructors with the default hasher.
+impl<K, V> OmniMap<K, V> {
     /// Returns a new `OmniMap` without allocated capacity.
     ///
+    /// This function requires the feature `std`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -118,21 +254,41 @@ where
     /// assert_eq!(map.len(), 0);
     /// assert_eq!(map.capacity(), 0);
     /// ```
+    #[cfg(feature = "std")]
     #[must_use]
     #[inline]
-    pub const fn new() -> Self {
-        OmniMap {
-            // Unallocated pointers.
-            entries: UnsafeBufferPointer::new(),
-            index: MapIndex::new_unallocated(),
-            cap: 0,
-            len: 0,
-            deleted: 0,
-        }
+    pub fn new() -> Self {
+        Self::with_hasher(DefaultHashBuilder::new())
+    }
+
+    /// Returns a new `OmniMap` without allocated capacity, which hashes the keys with keys
+    /// derived deterministically from `seed` instead of random keys.
+    ///
+    /// Maps created with the same seed hash the same keys identically, which makes the layout
+    /// reproducible in tests and snapshots. Prefer [`OmniMap::new`] when the keys can be chosen
+    /// by untrusted input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::hash::BuildHasher;
+    /// use omnimap::OmniMap;
+    ///
+    /// let a: OmniMap<&str, i32> = OmniMap::with_seed(42);
+    /// let b: OmniMap<&str, i32> = OmniMap::with_seed(42);
+    ///
+    /// assert_eq!(a.hasher().hash_one("key"), b.hasher().hash_one("key"));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_seed(seed: u64) -> Self {
+        Self::with_hasher(DefaultHashBuilder::with_seed(seed))
     }
 
     /// Creates a new `OmniMap` with the specified `capacity`.
     ///
+    /// This function requires the feature `std`.
+    ///
     /// # Panics
     ///
     /// This function will panic if capacity overflow occurs, or when allocation fails.
@@ -147,305 +303,570 @@ where
     /// assert_eq!(map.len(), 0);
     /// assert_eq!(map.capacity(), 10);
     /// ```
+    #[cfg(feature = "std")]
     #[must_use]
     #[inline]
     pub fn with_capacity(capacity: usize) -> Self {
-        if capacity == 0 {
-            return Self::new();
-        }
-
-        unsafe {
-            let cap = match Self::allocation_capacity(capacity, OnError::NoReturn) {
-                Ok(cap) => cap,
-                Err(_) => unreachable_unchecked(),
-            };
-
-            let mut instance = Self::new();
-
-            match instance.allocate::<true>(cap, OnError::NoReturn) {
-                Ok(_) => instance,
-                Err(_) => unreachable_unchecked(),
-            }
-        }
+        Self::with_capacity_and_hasher(capacity, DefaultHashBuilder::new())
     }
+}
 
-    /// Returns the allocated _usable_ capacity of the `OmniMap`.
-    ///
-    /// The actual allocated capacity is higher to maintain the load factor.
+impl<K, V, S> OmniMap<K, V, S> {
+    /// Returns a new `OmniMap` without allocated capacity, which uses `hash_builder` to hash the
+    /// keys.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::collections::hash_map::RandomState;
     /// use omnimap::OmniMap;
     ///
-    /// let map: OmniMap<i32, &str> = OmniMap::new();
-    /// assert_eq!(map.capacity(), 0);
+    /// let mut map = OmniMap::with_hasher(RandomState::new());
     ///
-    /// let map: OmniMap<i32, &str> = OmniMap::default();
-    /// assert_eq!(map.capacity(), 16);
+    /// map.insert(1, "a");
     ///
-    /// let map: OmniMap<i32, &str> = OmniMap::with_capacity(10);
-    /// assert_eq!(map.capacity(), 10);
+    /// assert_eq!(map.get(&1), Some(&"a"));
     /// ```
-    #[inline(always)]
-    pub const fn capacity(&self) -> usize {
-        (self.cap >> 3) * 7 + ((self.cap & 7) * 7 >> 3)
+    #[must_use]
+    #[inline]
+    pub const fn with_hasher(hash_builder: S) -> Self {
+        Self::with_hasher_in(hash_builder, Global)
     }
 
-    /// Returns the number of entries in the `OmniMap`.
+    /// Creates a new `OmniMap` with the specified `capacity`, which uses `hash_builder` to hash
+    /// the keys.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if capacity overflow occurs, or when allocation fails.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::collections::hash_map::RandomState;
     /// use omnimap::OmniMap;
     ///
-    /// let mut map = OmniMap::new();
+    /// let map: OmniMap<i32, &str, _> = OmniMap::with_capacity_and_hasher(10, RandomState::new());
     ///
     /// assert_eq!(map.len(), 0);
+    /// assert_eq!(map.capacity(), 10);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        Self::with_capacity_and_hasher_in(capacity, hash_builder, Global)
+    }
+}
+
+// Constructors with the default hasher and a custom allocator.
+impl<K, V, A: Allocator> OmniMap<K, V, DefaultHashBuilder, A> {
+    /// Returns a new `OmniMap` without allocated capacity, which allocates its memory with
+    /// `alloc`.
+    ///
+    /// This function requires the feature `std`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::{Global, OmniMap};
+    ///
+    /// let mut map = OmniMap::new_in(Global);
     ///
     /// map.insert(1, "a");
-    /// map.insert(2, "b");
     ///
-    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map.get(&1), Some(&"a"));
     /// ```
-    #[inline(always)]
-    pub const fn len(&self) -> usize {
-        self.len
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub fn new_in(alloc: A) -> Self {
+        Self::with_hasher_in(DefaultHashBuilder::new(), alloc)
     }
 
-    /// Checks if the `OmniMap` is empty.
+    /// Creates a new `OmniMap` with the specified `capacity`, which allocates its memory with
+    /// `alloc`.
+    ///
+    /// This function requires the feature `std`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if capacity overflow occurs, or when allocation fails.
     ///
     /// # Examples
     ///
     /// ```
-    /// use omnimap::OmniMap;
+    /// use omnimap::{Global, OmniMap};
     ///
-    /// let map: OmniMap<i32, &str> = OmniMap::new();
+    /// let map: OmniMap<i32, &str, _, _> = OmniMap::with_capacity_in(10, Global);
     ///
-    /// assert!(map.is_empty());
+    /// assert_eq!(map.len(), 0);
+    /// assert_eq!(map.capacity(), 10);
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self::with_capacity_and_hasher_in(capacity, DefaultHashBuilder::new(), alloc)
+    }
+}
+
+impl<K, V, S, A: Allocator> OmniMap<K, V, S, A> {
+    /// Returns a new `OmniMap` without allocated capacity, which uses `hash_builder` to hash the
+    /// keys and allocates its memory with `alloc`.
     ///
-    /// let mut map = OmniMap::new();
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::{Global, OmniMap, RandomState};
+    ///
+    /// let mut map = OmniMap::with_hasher_in(RandomState::with_seed(0), Global);
     ///
     /// map.insert(1, "a");
     ///
-    /// assert!(!map.is_empty());
+    /// assert_eq!(map.get(&1), Some(&"a"));
     /// ```
-    #[inline(always)]
-    pub const fn is_empty(&self) -> bool {
-        self.len == 0
+    #[must_use]
+    #[inline]
+    pub const fn with_hasher_in(hash_builder: S, alloc: A) -> Self {
+        OmniMap {
+            // Unallocated pointers.
+            entries: UnsafeBufferPointer::new(),
+            index: MapIndex::new_unallocated(),
+            cap: 0,
+            len: 0,
+            hash_builder,
+            alloc,
+        }
     }
 
-    /// Calculates the hash value for a key.
+    /// Creates a new `OmniMap` with the specified `capacity`, which uses `hash_builder` to hash
+    /// the keys and allocates its memory with `alloc`.
     ///
-    /// > Note: The hash method of the `key` may panic.
+    /// # Panics
+    ///
+    /// This function will panic if capacity overflow occurs, or when allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::{Global, OmniMap, RandomState};
+    ///
+    /// let map: OmniMap<i32, &str, _, _> =
+    ///     OmniMap::with_capacity_and_hasher_in(10, RandomState::with_seed(0), Global);
+    ///
+    /// assert_eq!(map.len(), 0);
+    /// assert_eq!(map.capacity(), 10);
+    /// ```
+    #[must_use]
     #[inline]
-    fn make_hash(key: &K) -> usize {
-        let mut hasher = DefaultHasher::new();
-        key.hash(&mut hasher);
-        hasher.finish() as usize
+    pub fn with_capacity_and_hasher_in(capacity: usize, hash_builder: S, alloc: A) -> Self {
+        let mut instance = Self::with_hasher_in(hash_builder, alloc);
+
+        if capacity == 0 {
+            return instance;
+        }
+
+        unsafe {
+            let cap = match allocation_capacity(capacity, OnError::NoReturn) {
+                Ok(cap) => cap,
+                Err(_) => unreachable_unchecked(),
+            };
+
+            match instance.allocate::<true>(cap, OnError::NoReturn) {
+                Ok(_) => instance,
+                Err(_) => unreachable_unchecked(),
+            }
+        }
     }
 
-    /// Returns the value that maintains the load factor for a given capacity `given`.
-    ///
-    /// This method doesn't check for arithmetic overflow.
+    /// Returns a reference to the hash builder of the map.
+    #[must_use]
+    #[inline(always)]
+    pub const fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    /// Returns a reference to the allocator of the map.
     #[must_use]
     #[inline(always)]
-    const fn allocation_capacity_unchecked(given: usize) -> usize {
-        ((given + 1) * 8) / 7
+    pub const fn allocator(&self) -> &A {
+        &self.alloc
     }
 
-    /// Returns the value that maintains the load factor for a given capacity `given`.
+    /// Returns the current entries as a slice.
     ///
-    /// This method checks for arithmetic overflow.
+    /// This method makes it safe to access the entries without worrying about the state of the
+    /// pointer.
     #[inline(always)]
-    const fn allocation_capacity(given: usize, on_err: OnError) -> Result<usize, AllocError> {
-        if let Some(plus_one) = given.checked_add(1) {
-            if let Some(mul_eight) = plus_one.checked_mul(8) {
-                // Can't overflow.
-                return Ok(mul_eight / 7);
-            }
+    pub(crate) const fn entries_slice(&self) -> &[Bucket<K, V>] {
+        if self.len == 0 {
+            return &[];
         }
-        Err(on_err.overflow())
+        unsafe { self.entries.as_slice(self.len) }
     }
 
-    /// Returns the next power of two of the current capacity.
+    /// Returns the current entries as a mutable slice.
     ///
-    /// This method panics when overflow occurs.
+    /// This method makes it safe to access the entries without worrying about the state of the
+    /// pointer.
     #[inline(always)]
-    const fn capacity_next_power_of_two(&self) -> usize {
-        match Self::allocation_capacity(self.cap, OnError::NoReturn) {
-            Ok(alloc_cap) => alloc_cap.next_power_of_two(),
-            Err(_) => unsafe { unreachable_unchecked() },
+    pub(crate) const fn entries_slice_mut(&mut self) -> &mut [Bucket<K, V>] {
+        if self.len == 0 {
+            return &mut [];
         }
+        unsafe { self.entries.as_slice_mut(self.len) }
     }
 
-    /// Allocates the specified `cap`.
-    ///
-    /// On error, the map's state will not be affected, therefore this method shall be the only
-    /// one used to allocate new instances, because it guards against partial allocations and
-    /// invalid states when allocation fails.
+    /// Returns a slice view of all the entries in the map.
     ///
-    /// if `INIT` is:
-    /// - `true`: this method will initialize the control tags of the index after allocation.
-    /// - `false`: the control tags of the allocated index will remain uninitialized.
+    /// # Examples
     ///
-    /// On total success, fields entries, index and `cap` are set according the new state.
+    /// ```
+    /// use omnimap::OmniMap;
     ///
-    /// Note: the size of `new_cap` must be greater than `0` and within the range of `isize::MAX`
-    /// bytes to be considered a valid size, but successful allocation remains not guaranteed.
+    /// let mut map = OmniMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
     ///
-    /// # Safety
+    /// let slice = map.as_slice();
     ///
-    /// This method should be called **only** when the map is not allocated.
-    fn allocate<const INIT: bool>(
-        &mut self,
-        cap: usize,
-        on_err: OnError,
-    ) -> Result<(), AllocError> {
-        unsafe {
-            let layout = self.entries.make_layout(cap, on_err)?;
-
-            let mut index = MapIndex::new_allocate_uninit(cap, on_err)?;
-
-            let dealloc_guard = defer!(cap, index.deallocate(*cap));
-
-            self.entries.allocate(layout, on_err)?;
+    /// assert_eq!(slice.len(), 2);
+    /// assert_eq!(slice.last(), Some((&2, &"b")));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn as_slice(&self) -> &Slice<K, V> {
+        Slice::from_entries(self.entries_slice())
+    }
 
-            dealloc_guard.deactivate();
+    /// Returns a mutable slice view of all the entries in the map.
+    ///
+    /// Only the values can be modified through the slice.
+    #[must_use]
+    #[inline]
+    pub const fn as_mut_slice(&mut self) -> &mut Slice<K, V> {
+        Slice::from_entries_mut(self.entries_slice_mut())
+    }
 
-            self.index = index;
-            self.cap = cap;
+    /// Returns a slice view of the entries in the specified `range`.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(slice)`: If the range is within bounds.
+    ///
+    /// - `None`: If the start of the range is greater than its end, or if the end of the range
+    ///   is greater than the length of the map.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// for i in 0..5 {
+    ///     map.insert(i, i * 10);
+    /// }
+    ///
+    /// let slice = map.get_range(1..3).unwrap();
+    ///
+    /// assert_eq!(slice.iter().collect::<Vec<_>>(), vec![(&1, &10), (&2, &20)]);
+    /// assert!(map.get_range(4..6).is_none());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn get_range<R: RangeBounds<usize>>(&self, range: R) -> Option<&Slice<K, V>> {
+        self.as_slice().get_range(range)
+    }
 
-            if INIT {
-                self.index.set_tags_empty(cap);
-            }
+    /// Returns a mutable slice view of the entries in the specified `range`.
+    ///
+    /// Only the values can be modified through the slice.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(slice)`: If the range is within bounds.
+    ///
+    /// - `None`: If the start of the range is greater than its end, or if the end of the range
+    ///   is greater than the length of the map.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    #[must_use]
+    #[inline]
+    pub fn get_range_mut<R: RangeBounds<usize>>(&mut self, range: R) -> Option<&mut Slice<K, V>> {
+        self.as_mut_slice().get_range_mut(range)
+    }
 
-            Ok(())
-        }
+    /// Searches the map for the key `key` with binary search, without hashing.
+    ///
+    /// The map must be sorted by key, e.g. with [`OmniMap::sort_keys`] or by inserting the keys
+    /// in order, otherwise the result is unspecified but memory-safe.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(index)`: If the key is found. If there are multiple matches, any of them can be
+    ///   returned.
+    ///
+    /// - `Err(index)`: If the key is not found, where `index` is the position at which the key
+    ///   can be inserted with [`OmniMap::shift_insert`] while maintaining the sorted order.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(log n).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// for i in [1, 3, 5, 7] {
+    ///     map.insert(i, i * 10);
+    /// }
+    ///
+    /// assert_eq!(map.binary_search_keys(&5), Ok(2));
+    /// assert_eq!(map.binary_search_keys(&4), Err(2));
+    ///
+    /// if let Err(index) = map.binary_search_keys(&4) {
+    ///     map.shift_insert(index, 4, 40);
+    /// }
+    ///
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&1, &3, &4, &5, &7]);
+    /// ```
+    #[inline]
+    pub fn binary_search_keys(&self, key: &K) -> Result<usize, usize>
+    where
+        K: Ord,
+    {
+        self.as_slice().binary_search_keys(key)
     }
 
-    /// Deallocates the entries and the index without calling `drop` on the initialized entries.
+    /// Searches the map with binary search, using the comparator function `f`.
     ///
-    /// Fields of capacity, length, and deleted counter will be reset to `0`.
+    /// The comparator must return the ordering of the entry relative to the target, and the map
+    /// must be sorted consistently with it, otherwise the result is unspecified but memory-safe.
     ///
-    /// # Safety
+    /// # Returns
     ///
-    /// Index and entries must be allocated before calling this method.
-    fn deallocate(&mut self) {
-        unsafe {
-            // Infallible, uncontrolled. Already allocated.
-            let layout = self.entries.make_layout_unchecked(self.cap);
-            self.entries.deallocate(layout);
-            self.index.deallocate(self.cap);
-        }
+    /// The same as [`OmniMap::binary_search_keys`].
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(log n).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 4);
+    /// map.insert("c", 9);
+    ///
+    /// assert_eq!(map.binary_search_by(|_, v| v.cmp(&4)), Ok(1));
+    /// assert_eq!(map.binary_search_by(|_, v| v.cmp(&10)), Err(3));
+    /// ```
+    #[inline]
+    pub fn binary_search_by<'a, F>(&'a self, f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&'a K, &'a V) -> Ordering,
+    {
+        self.as_slice().binary_search_by(f)
+    }
 
-        // Reset fields.
-        self.cap = 0;
-        self.len = 0;
-        self.deleted = 0;
+    /// Searches the map with binary search for the key `b` extracted by the function `f`.
+    ///
+    /// The map must be sorted by the extracted key, otherwise the result is unspecified but
+    /// memory-safe.
+    ///
+    /// # Returns
+    ///
+    /// The same as [`OmniMap::binary_search_keys`].
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(log n).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert("a", (1, 'x'));
+    /// map.insert("b", (4, 'y'));
+    /// map.insert("c", (9, 'z'));
+    ///
+    /// assert_eq!(map.binary_search_by_key(&9, |_, v| v.0), Ok(2));
+    /// assert_eq!(map.binary_search_by_key(&0, |_, v| v.0), Err(0));
+    /// ```
+    #[inline]
+    pub fn binary_search_by_key<'a, B, F>(&'a self, b: &B, f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&'a K, &'a V) -> B,
+        B: Ord,
+    {
+        self.as_slice().binary_search_by_key(b, f)
     }
 
-    /// Builds the index of the map according to the current entries and the capacity of the index.
-    /// This method should be called **only** after resetting the index.
-    const fn build_index(&mut self) {
-        let mut i = 0;
+    /// Returns the index of the partition point of the map according to the predicate `pred`.
+    ///
+    /// The map must be partitioned, i.e. all the entries for which the predicate returns `true`
+    /// must precede all the entries for which it returns `false`, otherwise the result is
+    /// unspecified but memory-safe.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(log n).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// for i in 0..10 {
+    ///     map.insert(i, i);
+    /// }
+    ///
+    /// assert_eq!(map.partition_point(|k, _| *k < 4), 4);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn partition_point<P>(&self, pred: P) -> usize
+    where
+        P: FnMut(&K, &V) -> bool,
+    {
+        self.as_slice().partition_point(pred)
+    }
+
+    /// Allocates the specified `cap`.
+    ///
+    /// On error, the map's state will not be affected, therefore this method shall be the only
+    /// one used to allocate new instances, because it guards against partial allocations and
+    /// invalid states when allocation fails.
+    ///
+    /// if `INIT` is:
+    /// - `true`: this method will initialize the control tags of the index after allocation.
+    /// - `false`: the control tags of the allocated index will remain uninitialized.
+    ///
+    /// On total success, fields entries, index and `cap` are set according the new state.
+    ///
+    /// Note: the size of `new_cap` must be greater than `0` and within the range of `isize::MAX`
+    /// bytes to be considered a valid size, but successful allocation remains not guaranteed.
+    ///
+    /// # Safety
+    ///
+    /// This method should be called **only** when the map is not allocated.
+    fn allocate<const INIT: bool>(
+        &mut self,
+        cap: usize,
+        on_err: OnError,
+    ) -> Result<(), AllocError> {
         unsafe {
-            while i < self.len {
-                let entry = self.entries.load(i);
-                let mut slot = entry.hash % self.cap;
+            let layout = self.entries.make_layout(cap, on_err)?;
 
-                'probing: loop {
-                    let tag = self.index.tag_ref_mut(slot);
-                    if tag.is_empty() {
-                        *tag = Tag::Occupied;
-                        self.index.store_entry_index(slot, i);
-                        break 'probing;
-                    }
+            let mut index = MapIndex::new_allocate_uninit(cap, &self.alloc, on_err)?;
 
-                    debug_assert!(
-                        !tag.is_deleted(),
-                        "Logic error: detected deleted slot while building index"
-                    );
+            let dealloc_guard = defer!(cap, index.deallocate(&self.alloc, *cap));
 
-                    slot = (slot + 1) % self.cap;
-                }
+            self.entries.allocate(&self.alloc, layout, on_err)?;
 
-                i += 1;
+            dealloc_guard.deactivate();
+
+            self.index = index;
+            self.cap = cap;
+
+            if INIT {
+                self.index.set_tags_empty(cap);
             }
+
+            Ok(())
         }
     }
+}
 
-    /// Decrements the index of all occupied slots with index value greater than `after` by using
-    /// linear search.
-    ///
-    /// The search domain is `[0, capacity - 1]`.
-    const fn decrement_index_linear(&mut self, after: usize) {
-        let mut i = 0;
-        unsafe {
-            while i < self.cap {
-                if self.index.read_tag(i).is_occupied() {
-                    let index = self.index.entry_index_ref_mut(i);
-                    if *index > after {
-                        *index -= 1;
-                    }
-                }
-                i += 1
-            }
+/// Returns the value that maintains the load factor for a given capacity `given`.
+///
+/// This function doesn't check for arithmetic overflow.
+#[must_use]
+#[inline(always)]
+const fn allocation_capacity_unchecked(given: usize) -> usize {
+    ((given + 1) * 8) / 7
+}
+
+/// Returns the value that maintains the load factor for a given capacity `given`.
+///
+/// This function checks for arithmetic overflow.
+#[inline(always)]
+const fn allocation_capacity(given: usize, on_err: OnError) -> Result<usize, AllocError> {
+    if let Some(plus_one) = given.checked_add(1) {
+        if let Some(mul_eight) = plus_one.checked_mul(8) {
+            // Can't overflow.
+            return Ok(mul_eight / 7);
         }
     }
+    Err(on_err.overflow())
+}
 
-    /// Decrements the index of occupied slots by using the hash value of each entry after `after`
-    /// to find its slot.
+// Index maintenance, which depends only on the cached hashes of the entries.
+impl<K, V, S, A: Allocator> OmniMap<K, V, S, A> {
+    /// Builds the index of the map according to the current entries and the capacity of the index.
+    /// This method should be called **only** after resetting the index.
+    #[inline(always)]
+    fn build_index(&mut self) {
+        self.build_index_from(0);
+    }
+
+    /// Adds the entries starting from `from` to the index according to their cached hashes.
     ///
-    /// The search domain starts from `after` as exclusive bound and ends with `inc_end` as
-    /// inclusive upper bound.
-    const fn decrement_index_hash(&mut self, after: usize, inc_end: usize) {
-        let mut i = after + 1;
+    /// The entries before `from` must be already indexed, and the entries after it must not be.
+    fn build_index_from(&mut self, from: usize) {
         unsafe {
-            while i <= inc_end {
+            for i in from..self.len {
                 let hash = self.entries.load(i).hash;
-                let mut slot = hash % self.cap;
-
-                'probing: loop {
-                    if self.index.read_tag(slot).is_occupied() {
-                        let index = self.index.entry_index_ref_mut(slot);
-                        if *index == i {
-                            *index -= 1;
-                            break 'probing;
-                        }
-                    }
-
-                    slot = (slot + 1) % self.cap
-                }
-
-                i += 1;
+                let slot = self.find_empty_slot(hash);
+                self.index.store(slot, Tag::full(hash), i, self.cap);
             }
         }
     }
 
-    /// Decrements the index of the occupied slots.
-    ///
-    /// Parameters:
-    ///  - `after`: the position to decrement after it.
-    ///  - `inc_end`: an **inclusive** upper bound for decrementing.
-    ///
-    /// Decrementing applies one of two methods to find the target slots.
+    /// Returns the first empty slot in the probe sequence of `hash`.
     ///
-    /// - If `inc_end - after` is greater than `capacity/2`, the search for the affected slots will
-    ///   be linear decrementing all encountered occupied slots in the index with value greater than
-    ///   `after` within the range `[0, capacity - 1]`.
+    /// # Safety
     ///
-    /// - If `inc_end - after` is less than or equal to `capacity/2`, the search for the target
-    ///   slots will be very specific using the hash value of the entries starting from offset
-    ///   `from + 1` to `inc_end` as an inclusive upper bound.
+    /// The index must be allocated and must have at least one empty slot.
     #[inline]
-    const fn decrement_index(&mut self, after: usize, inc_end: usize) {
-        let count = inc_end - after;
-        if count > self.cap / 2 {
-            self.decrement_index_linear(after);
+    unsafe fn find_empty_slot(&self, hash: usize) -> usize {
+        let width = Group::WIDTH.min(self.cap);
+        let mut pos = hash % self.cap;
+        loop {
+            let group = self.index.load_group(pos);
+            if let Some(bit) = group.match_empty().take(width).lowest_set_bit() {
+                return self.wrap_slot(pos + bit);
+            }
+            pos = self.wrap_slot(pos + width);
+        }
+    }
+
+    /// Wraps `slot` around the capacity of the index.
+    ///
+    /// `slot` must be less than twice the capacity.
+    #[inline(always)]
+    const fn wrap_slot(&self, slot: usize) -> usize {
+        if slot >= self.cap {
+            slot - self.cap
         } else {
-            // It has probing overhead, but it can skip large sequences.
-            self.decrement_index_hash(after, inc_end);
+            slot
         }
     }
 
@@ -454,107 +875,1861 @@ where
     #[inline(always)]
     fn reindex(&mut self) {
         unsafe { self.index.set_tags_empty(self.cap) };
-        self.deleted = 0;
         self.build_index();
     }
 
-    /// Shrinks or grows the allocated memory space to the specified `new_cap`.
-    ///
-    /// This method will also reset the index and rebuild it according to the new capacity.
+    /// Frees the occupied `slot` in the index by backward-shift deletion.
     ///
-    /// On error, the map's state will not be affected.
+    /// The following slots of the same probe sequence are shifted back to close the gap, so that
+    /// the sequence remains contiguous and no tombstones are needed. A slot is shifted only if the
+    /// gap isn't before the ideal slot of its entry, otherwise the entry would become unreachable.
     ///
     /// # Safety
     ///
-    /// - Index and entries must be allocated before calling this method.
-    ///
-    /// - `new_cap` must be greater than `0` and the current length.
-    ///
-    fn reallocate_reindex(&mut self, new_cap: usize, on_err: OnError) -> Result<(), AllocError> {
-        unsafe {
-            let new_layout = self.entries.make_layout(new_cap, on_err)?;
-
-            let mut new_index = MapIndex::new_allocate_uninit(new_cap, on_err)?;
+    /// `slot` must be occupied, and the entries referenced by all other occupied slots must be
+    /// initialized, because their cached hashes are read.
+    const unsafe fn erase_slot(&mut self, slot: usize) {
+        let mut gap = slot;
+        let mut next = (slot + 1) % self.cap;
+
+        while self.index.read_tag(next).is_occupied() {
+            let index = self.index.read_entry_index(next);
+            let ideal = self.entries.load(index).hash % self.cap;
+
+            // The distances are cyclic, the gap can be shifted into if it is within the range
+            // `[ideal, next)`.
+            let probe_distance = (next + self.cap - ideal) % self.cap;
+            let gap_distance = (next + self.cap - gap) % self.cap;
+
+            if probe_distance >= gap_distance {
+                // The fingerprint moves with the entry.
+                let tag = self.index.read_tag(next);
+                self.index.store(gap, tag, index, self.cap);
+                gap = next;
+            }
 
-            let dealloc_guard = defer!(new_cap, new_index.deallocate(*new_cap));
+            next = (next + 1) % self.cap;
+        }
 
-            let current_layout = self.entries.make_layout_unchecked(self.cap);
+        self.index.store_tag(gap, Tag::EMPTY, self.cap);
+    }
+}
 
-            self.entries
-                .reallocate(current_layout, new_layout, self.len, on_err)?;
+// Core implementation
+impl<K, V, S, A> OmniMap<K, V, S, A>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    const DEFAULT_CAPACITY: usize = 16;
 
-            self.index.deallocate(self.cap);
-            debug_assert!(self.index.not_allocated());
+    /// Returns the allocated _usable_ capacity of the `OmniMap`.
+    ///
+    /// The actual allocated capacity is higher to maintain the load factor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let map: OmniMap<i32, &str> = OmniMap::new();
+    /// assert_eq!(map.capacity(), 0);
+    ///
+    /// let map: OmniMap<i32, &str> = OmniMap::default();
+    /// assert_eq!(map.capacity(), 16);
+    ///
+    /// let map: OmniMap<i32, &str> = OmniMap::with_capacity(10);
+    /// assert_eq!(map.capacity(), 10);
+    /// ```
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        (self.cap >> 3) * 7 + (((self.cap & 7) * 7) >> 3)
+    }
 
-            dealloc_guard.deactivate();
+    /// Returns the number of entries in the `OmniMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// assert_eq!(map.len(), 0);
+    ///
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
 
-            self.index = new_index;
-            self.cap = new_cap;
-            self.deleted = 0;
+    /// Checks if the `OmniMap` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let map: OmniMap<i32, &str> = OmniMap::new();
+    ///
+    /// assert!(map.is_empty());
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.insert(1, "a");
+    ///
+    /// assert!(!map.is_empty());
+    /// ```
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
 
-            self.index.set_tags_empty(new_cap);
-            self.build_index();
+    /// Returns a reference to the entry at `index`.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be within the bounds of the initialized entries.
+    #[inline(always)]
+    pub(crate) const unsafe fn bucket(&self, index: usize) -> &Bucket<K, V> {
+        debug_assert!(index < self.len);
+        self.entries.load(index)
+    }
 
-            Ok(())
+    /// Returns a mutable reference to the entry at `index`.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be within the bounds of the initialized entries.
+    #[inline(always)]
+    pub(crate) const unsafe fn bucket_mut(&mut self, index: usize) -> &mut Bucket<K, V> {
+        debug_assert!(index < self.len);
+        self.entries.load_mut(index)
+    }
+
+    /// Calculates the hash value for a key.
+    ///
+    /// The key can be passed in any borrowed form `Q` of `K`, because `Borrow` guarantees that
+    /// the borrowed form hashes the same way as the owned key.
+    ///
+    /// > Note: The hash method of the `key` may panic.
+    #[inline]
+    fn make_hash<Q>(&self, key: &Q) -> usize
+    where
+        Q: ?Sized + Hash,
+    {
+        self.hash_builder.hash_one(key) as usize
+    }
+
+    /// Returns the next power of two of the current capacity.
+    ///
+    /// This method panics when overflow occurs.
+    #[inline(always)]
+    const fn capacity_next_power_of_two(&self) -> usize {
+        match allocation_capacity(self.cap, OnError::NoReturn) {
+            Ok(alloc_cap) => alloc_cap.next_power_of_two(),
+            Err(_) => unsafe { unreachable_unchecked() },
         }
     }
 
-    /// Reclaims deleted slots if suitable or reserves more capacity according to the load factor.
+    /// Deallocates the entries and the index without calling `drop` on the initialized entries.
     ///
-    /// This method panics when overflow occurs or when allocation fails.
-    fn reclaim_or_reserve(&mut self) {
-        if self.len < self.cap >> 1 {
-            // Reclaiming deleted slots without reallocation.
-            self.reindex();
+    /// Fields of capacity and length will be reset to `0`.
+    ///
+    /// # Safety
+    ///
+    /// Index and entries must be allocated before calling this method.
+    fn deallocate(&mut self) {
+        unsafe {
+            // Infallible, uncontrolled. Already allocated.
+            let layout = self.entries.make_layout_unchecked(self.cap);
+            self.entries.deallocate(&self.alloc, layout);
+            self.index.deallocate(&self.alloc, self.cap);
+        }
+
+        // Reset fields.
+        self.cap = 0;
+        self.len = 0;
+    }
+
+    /// Decrements the index of all occupied slots with index value greater than `after` and less
+    /// than or equal to `inc_end` by using linear search.
+    ///
+    /// The search domain is `[0, capacity - 1]`.
+    const fn decrement_index_linear(&mut self, after: usize, inc_end: usize) {
+        let mut i = 0;
+        unsafe {
+            while i < self.cap {
+                if self.index.read_tag(i).is_occupied() {
+                    let index = self.index.entry_index_ref_mut(i);
+                    if *index > after && *index <= inc_end {
+                        *index -= 1;
+                    }
+                }
+                i += 1
+            }
+        }
+    }
+
+    /// Decrements the index of occupied slots by using the hash value of each entry after `after`
+    /// to find its slot.
+    ///
+    /// The search domain starts from `after` as exclusive bound and ends with `inc_end` as
+    /// inclusive upper bound.
+    const fn decrement_index_hash(&mut self, after: usize, inc_end: usize) {
+        let mut i = after + 1;
+        unsafe {
+            while i <= inc_end {
+                let hash = self.entries.load(i).hash;
+                let tag = Tag::full(hash);
+                let mut slot = hash % self.cap;
+
+                'probing: loop {
+                    // The fingerprint skips most of the slots of other entries.
+                    if self.index.read_tag(slot).is(tag) {
+                        let index = self.index.entry_index_ref_mut(slot);
+                        if *index == i {
+                            *index -= 1;
+                            break 'probing;
+                        }
+                    }
+
+                    slot = (slot + 1) % self.cap
+                }
+
+                i += 1;
+            }
+        }
+    }
+
+    /// Decrements the index of the occupied slots.
+    ///
+    /// Parameters:
+    ///  - `after`: the position to decrement after it.
+    ///  - `inc_end`: an **inclusive** upper bound for decrementing.
+    ///
+    /// Decrementing applies one of two methods to find the target slots.
+    ///
+    /// - If `inc_end - after` is greater than `capacity/2`, the search for the affected slots will
+    ///   be linear decrementing all encountered occupied slots in the index with value within the
+    ///   range `(after, inc_end]` within the range `[0, capacity - 1]`.
+    ///
+    /// - If `inc_end - after` is less than or equal to `capacity/2`, the search for the target
+    ///   slots will be very specific using the hash value of the entries starting from offset
+    ///   `from + 1` to `inc_end` as an inclusive upper bound.
+    #[inline]
+    const fn decrement_index(&mut self, after: usize, inc_end: usize) {
+        let count = inc_end - after;
+        if count > self.cap / 2 {
+            self.decrement_index_linear(after, inc_end);
         } else {
-            // Reallocation.
-            let result = if likely(self.cap != 0) {
-                let new_cap = self.capacity_next_power_of_two();
-                self.reallocate_reindex(new_cap, OnError::NoReturn)
-            } else {
-                self.allocate::<true>(4, OnError::NoReturn)
-            };
-            // Hints the compiler that the error branch can be eliminated from the call chain.
-            match result {
-                Ok(_) => (),
-                Err(_) => unsafe { unreachable_unchecked() },
+            // It has probing overhead, but it can skip large sequences.
+            self.decrement_index_hash(after, inc_end);
+        }
+    }
+
+    /// Increments the index of all occupied slots with index value greater than or equal to
+    /// `from` and less than or equal to `inc_end` by using linear search.
+    ///
+    /// The search domain is `[0, capacity - 1]`.
+    const fn increment_index_linear(&mut self, from: usize, inc_end: usize) {
+        let mut i = 0;
+        unsafe {
+            while i < self.cap {
+                if self.index.read_tag(i).is_occupied() {
+                    let index = self.index.entry_index_ref_mut(i);
+                    if *index >= from && *index <= inc_end {
+                        *index += 1;
+                    }
+                }
+                i += 1
             }
         }
     }
 
-    /// Tries to reserve `additional` capacity.
+    /// Increments the index of occupied slots by using the hash value of each entry from `from`
+    /// to find its slot.
     ///
-    /// All internal calls are checked, with result depends on the error handling context `on_err`.
-    fn reserve_additional(&mut self, additional: usize, on_err: OnError) -> Result<(), AllocError> {
-        if likely(additional != 0) {
-            let extra_cap = Self::allocation_capacity(additional, on_err)?;
-            if likely(self.cap != 0) {
-                match self.cap.checked_add(extra_cap) {
-                    Some(new_cap) => self.reallocate_reindex(new_cap, on_err),
-                    None => Err(on_err.overflow()),
+    /// The search domain starts from `from` as inclusive bound and ends with `inc_end` as
+    /// inclusive upper bound, and it is traversed in reverse order, so that an incremented value
+    /// can't be confused with the value of the next entry.
+    const fn increment_index_hash(&mut self, from: usize, inc_end: usize) {
+        let mut i = inc_end + 1;
+        unsafe {
+            while i > from {
+                i -= 1;
+
+                let hash = self.entries.load(i).hash;
+                let tag = Tag::full(hash);
+                let mut slot = hash % self.cap;
+
+                'probing: loop {
+                    // The fingerprint skips most of the slots of other entries.
+                    if self.index.read_tag(slot).is(tag) {
+                        let index = self.index.entry_index_ref_mut(slot);
+                        if *index == i {
+                            *index += 1;
+                            break 'probing;
+                        }
+                    }
+
+                    slot = (slot + 1) % self.cap
                 }
-            } else {
-                self.allocate::<true>(extra_cap, on_err)
             }
+        }
+    }
+
+    /// Increments the index of the occupied slots.
+    ///
+    /// Parameters:
+    ///  - `from`: the first position to increment.
+    ///  - `inc_end`: an **inclusive** upper bound for incrementing.
+    ///
+    /// Incrementing mirrors [`decrement_index`](Self::decrement_index), and applies the same two
+    /// methods to find the target slots according to the size of the range.
+    #[inline]
+    const fn increment_index(&mut self, from: usize, inc_end: usize) {
+        let count = inc_end + 1 - from;
+        if count > self.cap / 2 {
+            self.increment_index_linear(from, inc_end);
         } else {
-            Ok(())
+            // It has probing overhead, but it can skip large sequences.
+            self.increment_index_hash(from, inc_end);
         }
     }
 
-    /// Reserves capacity for `additional` elements in advance.
+    /// Returns the occupied slot that references the entry at `index`.
     ///
-    /// The resulting capacity will be equal to `self.capacity() + additional` or _more_ to
-    /// maintain the load factor.
+    /// The slot is found by probing with the hash value of the entry.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than the length.
+    const unsafe fn find_slot_of(&self, index: usize) -> usize {
+        let hash = self.entries.load(index).hash;
+        let tag = Tag::full(hash);
+        let mut slot = hash % self.cap;
+        loop {
+            if self.index.read_tag(slot).is(tag) && self.index.read_entry_index(slot) == index {
+                return slot;
+            }
+            slot = (slot + 1) % self.cap
+        }
+    }
+
+    /// Moves the entry at `from` to `to`, and shifts the entries in between.
+    ///
+    /// Only the slots of the shifted entries and the moved entry are updated.
+    ///
+    /// # Safety
+    ///
+    /// `from` and `to` must be less than the length.
+    unsafe fn move_index_unchecked(&mut self, from: usize, to: usize) {
+        debug_assert!(from < self.len && to < self.len);
+
+        if from == to {
+            return;
+        }
+
+        // Call order matters, the slot must be found before the index is updated.
+        let slot = self.find_slot_of(from);
+
+        if from < to {
+            self.decrement_index(from, to);
+            self.entries.as_slice_mut(self.len)[from..=to].rotate_left(1);
+        } else {
+            self.increment_index(to, from - 1);
+            self.entries.as_slice_mut(self.len)[to..=from].rotate_right(1);
+        }
+
+        self.index.store_entry_index(slot, to);
+    }
+
+    /// Shrinks or grows the allocated memory space to the specified `new_cap`.
+    ///
+    /// This method will also reset the index and rebuild it according to the new capacity.
+    ///
+    /// On error, the map's state will not be affected.
+    ///
+    /// # Safety
+    ///
+    /// - Index and entries must be allocated before calling this method.
+    ///
+    /// - `new_cap` must be greater than `0` and the current length.
+    ///
+    fn reallocate_reindex(&mut self, new_cap: usize, on_err: OnError) -> Result<(), AllocError> {
+        unsafe {
+            let new_layout = self.entries.make_layout(new_cap, on_err)?;
+
+            let mut new_index = MapIndex::new_allocate_uninit(new_cap, &self.alloc, on_err)?;
+
+            let dealloc_guard = defer!(new_cap, new_index.deallocate(&self.alloc, *new_cap));
+
+            let current_layout = self.entries.make_layout_unchecked(self.cap);
+
+            self.entries
+                .reallocate(&self.alloc, current_layout, new_layout, self.len, on_err)?;
+
+            self.index.deallocate(&self.alloc, self.cap);
+            debug_assert!(self.index.not_allocated());
+
+            dealloc_guard.deactivate();
+
+            self.index = new_index;
+            self.cap = new_cap;
+
+            self.index.set_tags_empty(new_cap);
+            self.build_index();
+
+            Ok(())
+        }
+    }
+
+    /// Reserves more capacity according to the load factor.
+    ///
+    /// This method panics when overflow occurs or when allocation fails.
+    fn grow(&mut self) {
+        let result = if likely(self.cap != 0) {
+            let new_cap = self.capacity_next_power_of_two();
+            self.reallocate_reindex(new_cap, OnError::NoReturn)
+        } else {
+            self.allocate::<true>(4, OnError::NoReturn)
+        };
+        // Hints the compiler that the error branch can be eliminated from the call chain.
+        match result {
+            Ok(_) => (),
+            Err(_) => unsafe { unreachable_unchecked() },
+        }
+    }
+
+    /// Tries to reserve `additional` capacity.
+    ///
+    /// All internal calls are checked, with result depends on the error handling context `on_err`.
+    fn reserve_additional(&mut self, additional: usize, on_err: OnError) -> Result<(), AllocError> {
+        if likely(additional != 0) {
+            let extra_cap = allocation_capacity(additional, on_err)?;
+            if likely(self.cap != 0) {
+                match self.cap.checked_add(extra_cap) {
+                    Some(new_cap) => self.reallocate_reindex(new_cap, on_err),
+                    None => Err(on_err.overflow()),
+                }
+            } else {
+                self.allocate::<true>(extra_cap, on_err)
+            }
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Reserves capacity for `additional` elements in advance.
+    ///
+    /// The resulting capacity will be equal to `self.capacity() + additional` or _more_ to
+    /// maintain the load factor.
+    ///
+    /// This method is no-op if `additional` is `0`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if capacity overflow occurs, or when allocation fails.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// // The allocated capacity with first insert is 4.
+    /// map.insert(1, "a");
+    ///
+    /// // Reserve space for 10 more elements
+    /// map.reserve(10);
+    ///
+    /// // The capacity is now 14
+    /// assert_eq!(map.capacity(), 14);
+    /// ```
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        match self.reserve_additional(additional, OnError::NoReturn) {
+            Ok(_) => (),
+            // Hints the compiler that the error branch can be eliminated from the call chain.
+            Err(_) => unsafe { unreachable_unchecked() },
+        };
+    }
+
+    /// Tries to reserve capacity for `additional` elements in advance.
+    ///
+    /// This method is semantically equivalent to [`OmniMap::reserve`], except that it returns an
+    /// error instead of panicking when the allocation fails.
+    ///
+    /// The resulting capacity will be equal to `self.capacity() + additional` or _more_ to
+    /// maintain the load factor.
+    ///
+    /// This method is no-op if `additional` is `0`.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::{AllocError, OmniMap};
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// // The allocated capacity with first insert is 4.
+    /// map.insert(1, "a");
+    ///
+    /// // Try reserve space for very large number of elements.
+    /// let mut result = map.try_reserve(usize::MAX);
+    ///
+    /// // Result must be error.
+    /// assert!(matches!(result.err().unwrap(), AllocError::Overflow));
+    ///
+    /// // The capacity remains 3
+    /// assert_eq!(map.capacity(), 3);
+    ///
+    /// // Reserve space for 10 more elements
+    /// result = map.try_reserve(10);
+    ///
+    /// assert!(result.is_ok());
+    ///
+    /// // The capacity is now 14
+    /// assert_eq!(map.capacity(), 14);
+    /// ```
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        self.reserve_additional(additional, OnError::ReturnErr)
+    }
+
+    /// Finds the slot of the key in the index.
+    ///
+    /// If the key already exists, the returned `slot` is the index of its slot and `entry`
+    /// is the index of its entry.
+    ///
+    /// If the key doesn't exist, the retuned `slot` is free for inserting and the value
+    /// of `entry` is an **invalid** index.
+    ///
+    /// # Safety
+    ///
+    /// Before using `entry`, its value must be checked first with `entry_exists()` method,
+    /// because its value can be an invalid index.
+    fn find<Q>(&self, hash: usize, key: &Q) -> FindResult
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+    {
+        unsafe {
+            let tag = Tag::full(hash);
+            // A group wider than the index wraps around, and only its first `cap` positions are
+            // distinct slots.
+            let width = Group::WIDTH.min(self.cap);
+            let mut pos = hash % self.cap;
+            // For all valid models: (empty slots exist) -> (unbounded loop can't be infinite).
+            loop {
+                let group = self.index.load_group(pos);
+
+                // Only the slots with a matching fingerprint are compared with the key. The
+                // matches after an empty slot belong to other probe sequences, and they can't
+                // be equal to the key.
+                for bit in group.match_tag(tag).take(width) {
+                    let slot = self.wrap_slot(pos + bit);
+                    let entry = self.index.read_entry_index(slot);
+                    if self.entries.load(entry).key.borrow() == key {
+                        return FindResult { slot, entry };
+                    }
+                }
+
+                // The probe sequence ends at the first empty slot.
+                if let Some(bit) = group.match_empty().take(width).lowest_set_bit() {
+                    return FindResult::just_slot(self.wrap_slot(pos + bit));
+                }
+
+                pos = self.wrap_slot(pos + width);
+            }
+        }
+    }
+
+    /// Inserts a key-value pair into the map.
+    /// If the map did not have this key present, `None` is returned.
+    /// If the map did have this key present, the value is updated, and the old value is returned.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to insert or update.
+    ///
+    /// - `value`: The value to associate with the key.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if capacity overflow occurs, or when allocation fails.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) Amortized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    ///  // When inserting a new key-value pair, None is returned
+    ///  map.insert(1, "a");
+    ///  map.insert(2, "b");
+    ///
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// assert_eq!(map.get(&2), Some(&"b"));
+    ///
+    /// // Update the value for an existing key
+    /// let old_value = map.insert(1, "c");
+    ///
+    /// // The old value is returned
+    /// assert_eq!(old_value, Some("a"));
+    ///
+    /// // The value is updated
+    /// assert_eq!(map.get(&1), Some(&"c"));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.reserve_one();
+
+        let hash = self.make_hash(&key);
+
+        let result = self.find(hash, &key);
+
+        if result.entry_exists() {
+            let entry = unsafe { self.entries.load_mut(result.entry) };
+            let old_val = mem::replace(&mut entry.value, value);
+            return Some(old_val);
+        };
+
+        unsafe { self.insert_unique(result.slot, hash, key, value) };
+
+        // Key was new and inserted.
+        None
+    }
+
+    /// Inserts a key-value pair into the map, and returns the index of the entry.
+    ///
+    /// If the map did have this key present, the value is updated in place, and the old value is
+    /// returned with the index of the entry.
+    ///
+    /// # Returns
+    ///
+    /// - `(index, None)`: If the key was new and appended at `index`.
+    ///
+    /// - `(index, Some(old_value))`: If the key existed at `index`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if capacity overflow occurs, or when allocation fails.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) Amortized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// assert_eq!(map.insert_full("a", 1), (0, None));
+    /// assert_eq!(map.insert_full("b", 2), (1, None));
+    /// assert_eq!(map.insert_full("a", 3), (0, Some(1)));
+    /// ```
+    #[inline]
+    pub fn insert_full(&mut self, key: K, value: V) -> (usize, Option<V>) {
+        self.reserve_one();
+
+        let hash = self.make_hash(&key);
+
+        let result = self.find(hash, &key);
+
+        if result.entry_exists() {
+            let entry = unsafe { self.entries.load_mut(result.entry) };
+            let old_val = mem::replace(&mut entry.value, value);
+            return (result.entry, Some(old_val));
+        };
+
+        let index = unsafe { self.insert_unique(result.slot, hash, key, value) };
+
+        (index, None)
+    }
+
+    /// Inserts a key-value pair into the map at the specified `index`, and shifts all entries
+    /// after it to the right.
+    ///
+    /// If the map did have this key present, the value is updated, and the entry is moved to
+    /// `index`.
+    ///
+    /// # Returns
+    ///
+    /// - `None`: If the key was new and inserted at `index`.
+    ///
+    /// - `Some(old_value)`: If the key existed and moved to `index`.
+    ///
+    /// # Panics
+    ///
+    /// - If `index` is out of bounds, valid indices are `[0, len]` when the key is new, and
+    ///   `[0, len - 1]` when the key exists.
+    ///
+    /// - If capacity overflow occurs, or when allocation fails.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("c", 3);
+    ///
+    /// assert_eq!(map.shift_insert(1, "b", 2), None);
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&"a", &"b", &"c"]);
+    ///
+    /// // Existing keys are moved.
+    /// assert_eq!(map.shift_insert(0, "c", 30), Some(3));
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&"c", &"a", &"b"]);
+    /// ```
+    #[inline]
+    pub fn shift_insert(&mut self, index: usize, key: K, value: V) -> Option<V> {
+        self.reserve_one();
+
+        let hash = self.make_hash(&key);
+
+        let result = self.find(hash, &key);
+
+        if result.entry_exists() {
+            assert!(index < self.len, "Index out of bounds.");
+            unsafe {
+                let entry = self.entries.load_mut(result.entry);
+                let old_val = mem::replace(&mut entry.value, value);
+                self.move_index_unchecked(result.entry, index);
+                return Some(old_val);
+            }
+        };
+
+        assert!(index <= self.len, "Index out of bounds.");
+
+        unsafe { self.shift_insert_unique(index, result.slot, hash, key, value) };
+
+        None
+    }
+
+    /// Inserts a key-value pair into the map before the entry with the key `anchor`.
+    ///
+    /// If the map did have this key present, the value is updated, and the entry is moved before
+    /// `anchor`.
+    ///
+    /// The `anchor` may be any borrowed form of the key type, but `Hash` and `Eq` on the borrowed
+    /// form must match those of the key type.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(None)`: If the key was new and inserted before `anchor`.
+    ///
+    /// - `Ok(Some(old_value))`: If the key existed and moved before `anchor`.
+    ///
+    /// - `Err((key, value))`: If `anchor` doesn't exist, the map is not modified.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if capacity overflow occurs, or when allocation fails.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("c", 3);
+    ///
+    /// assert_eq!(map.insert_before(&"c", "b", 2), Ok(None));
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&"a", &"b", &"c"]);
+    ///
+    /// // Anchor doesn't exist.
+    /// assert_eq!(map.insert_before(&"z", "d", 4), Err(("d", 4)));
+    /// ```
+    #[inline]
+    pub fn insert_before<Q>(&mut self, anchor: &Q, key: K, value: V) -> Result<Option<V>, (K, V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.insert_at_anchor::<false, Q>(anchor, key, value)
+    }
+
+    /// Inserts a key-value pair into the map after the entry with the key `anchor`.
+    ///
+    /// If the map did have this key present, the value is updated, and the entry is moved after
+    /// `anchor`.
+    ///
+    /// The `anchor` may be any borrowed form of the key type, but `Hash` and `Eq` on the borrowed
+    /// form must match those of the key type.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(None)`: If the key was new and inserted after `anchor`.
+    ///
+    /// - `Ok(Some(old_value))`: If the key existed and moved after `anchor`.
+    ///
+    /// - `Err((key, value))`: If `anchor` doesn't exist, the map is not modified.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if capacity overflow occurs, or when allocation fails.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("c", 3);
+    ///
+    /// assert_eq!(map.insert_after(&"a", "b", 2), Ok(None));
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&"a", &"b", &"c"]);
+    ///
+    /// // Anchor doesn't exist.
+    /// assert_eq!(map.insert_after(&"z", "d", 4), Err(("d", 4)));
+    /// ```
+    #[inline]
+    pub fn insert_after<Q>(&mut self, anchor: &Q, key: K, value: V) -> Result<Option<V>, (K, V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.insert_at_anchor::<true, Q>(anchor, key, value)
+    }
+
+    /// Inserts a key-value pair next to the entry with the key `anchor`.
+    ///
+    /// if `AFTER` is:
+    /// - `true`: the entry is placed right after `anchor`.
+    /// - `false`: the entry is placed right before `anchor`.
+    fn insert_at_anchor<const AFTER: bool, Q>(
+        &mut self,
+        anchor: &Q,
+        key: K,
+        value: V,
+    ) -> Result<Option<V>, (K, V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        let anchor_index = match self.get_index_of(anchor) {
+            Some(index) => index,
+            None => return Err((key, value)),
+        };
+
+        self.reserve_one();
+
+        let hash = self.make_hash(&key);
+
+        let result = self.find::<K>(hash, &key);
+
+        if result.entry_exists() {
+            if result.entry == anchor_index {
+                // The key is the anchor itself, its position doesn't change.
+                let entry = unsafe { self.entries.load_mut(result.entry) };
+                return Ok(Some(mem::replace(&mut entry.value, value)));
+            }
+            // The anchor is shifted to the left if the entry was before it.
+            let mut index = if result.entry < anchor_index {
+                anchor_index - 1
+            } else {
+                anchor_index
+            };
+            if AFTER {
+                index += 1;
+            }
+            unsafe {
+                let entry = self.entries.load_mut(result.entry);
+                let old_val = mem::replace(&mut entry.value, value);
+                self.move_index_unchecked(result.entry, index);
+                return Ok(Some(old_val));
+            }
+        }
+
+        let index = if AFTER {
+            anchor_index + 1
+        } else {
+            anchor_index
+        };
+
+        unsafe { self.shift_insert_unique(index, result.slot, hash, key, value) };
+
+        Ok(None)
+    }
+
+    /// Makes sure that the map can take one more entry without exceeding the load factor.
+    ///
+    /// This method panics when overflow occurs or when allocation fails.
+    #[inline(always)]
+    pub(crate) fn reserve_one(&mut self) {
+        if unlikely(self.len == self.capacity()) {
+            self.grow();
+        }
+    }
+
+    /// Makes sure that the map can take `additional` more entries without exceeding the load
+    /// factor, reserving only the capacity that is missing.
+    ///
+    /// This method panics when overflow occurs or when allocation fails.
+    #[inline]
+    pub(crate) fn reserve_free(&mut self, additional: usize) {
+        let free = self.capacity() - self.len;
+        if additional > free {
+            match self.reserve_additional(additional - free, OnError::NoReturn) {
+                Ok(_) => (),
+                // Hints the compiler that the error branch can be eliminated from the call chain.
+                Err(_) => unsafe { unreachable_unchecked() },
+            }
+        }
+    }
+
+    /// Stores a new entry at the end of the entries and occupies the free `slot` in the index.
+    ///
+    /// Returns the index of the stored entry.
+    ///
+    /// # Safety
+    ///
+    /// - The map must have a free capacity for one more entry.
+    ///
+    /// - `slot` must be the free slot returned by `find` for the same `hash` and `key`, and no
+    ///   modifications to the map must have been made in between.
+    #[inline]
+    pub(crate) unsafe fn insert_unique(
+        &mut self,
+        slot: usize,
+        hash: usize,
+        key: K,
+        value: V,
+    ) -> usize {
+        let index = self.len;
+        self.insert_unique_at(index, slot, hash, key, value);
+        index
+    }
+
+    /// Stores a new entry at `index` and occupies the free `slot` in the index.
+    ///
+    /// The offset `index` of the entries must be vacant.
+    #[inline(always)]
+    unsafe fn insert_unique_at(
+        &mut self,
+        index: usize,
+        slot: usize,
+        hash: usize,
+        key: K,
+        value: V,
+    ) {
+        debug_assert!(
+            self.index.read_tag(slot).is_empty(),
+            "Logic error: attempt to overwrite a non-empty slot while inserting"
+        );
+
+        self.index.store(slot, Tag::full(hash), index, self.cap);
+        self.entries.store(index, Bucket::new(key, value, hash));
+
+        self.len += 1;
+    }
+
+    /// Stores a new entry at the specified `index` and occupies the free `slot` in the index.
+    ///
+    /// The entries starting from `index` are shifted to the right, and their slots are updated.
+    ///
+    /// # Safety
+    ///
+    /// - The map must have a free capacity for one more entry.
+    ///
+    /// - `index` must be less than or equal to the length.
+    ///
+    /// - `slot` must be the free slot returned by `find` for the same `hash` and `key`, and no
+    ///   modifications to the map must have been made in between.
+    #[inline]
+    pub(crate) unsafe fn shift_insert_unique(
+        &mut self,
+        index: usize,
+        slot: usize,
+        hash: usize,
+        key: K,
+        value: V,
+    ) {
+        debug_assert!(index <= self.len);
+
+        if index == self.len {
+            self.insert_unique(slot, hash, key, value);
+            return;
+        }
+
+        // Call order matters, the index must be updated before shifting the entries.
+        self.increment_index(index, self.len - 1);
+        self.entries.shift_right(index, self.len - index);
+
+        self.insert_unique_at(index, slot, hash, key, value);
+    }
+
+    /// Gets the entry of the specified `key` for in-place manipulation.
+    ///
+    /// The key is hashed and searched for only once, and the returned entry keeps the result.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if capacity overflow occurs, or when allocation fails.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) Amortized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// for word in ["a", "b", "a", "c", "a"] {
+    ///     *map.entry(word).or_insert(0) += 1;
+    /// }
+    ///
+    /// assert_eq!(map.get(&"a"), Some(&3));
+    /// assert_eq!(map.get(&"b"), Some(&1));
+    /// assert_eq!(map.get(&"c"), Some(&1));
+    ///
+    /// // The order of the first insertion is preserved.
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&"a", &"b", &"c"]);
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, S, A> {
+        self.reserve_one();
+
+        let hash = self.make_hash(&key);
+
+        let result = self.find(hash, &key);
+
+        if result.entry_exists() {
+            Entry::Occupied(OccupiedEntry::new(self, result.slot, result.entry))
+        } else {
+            Entry::Vacant(VacantEntry::new(self, key, hash, result.slot))
+        }
+    }
+
+    /// Retrieves a value by its `key`.
+    ///
+    /// The `key` may be any borrowed form of the key type, but `Hash` and `Eq` on the borrowed
+    /// form must match those of the key type.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(&value)`: if the key is found.
+    ///
+    /// - `None`: if the key does not exist.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    ///  map.insert(1, "a");
+    ///
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    ///
+    /// // Key does not exist
+    /// assert_eq!(map.get(&2), None);
+    ///
+    /// // Keys can be looked up by their borrowed form.
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.insert(String::from("a"), 1);
+    ///
+    /// assert_eq!(map.get("a"), Some(&1));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        let hash = self.make_hash(key);
+
+        let result = self.find(hash, key);
+
+        if result.entry_exists() {
+            let value = unsafe { &self.entries.load(result.entry).value };
+            return Some(value);
+        }
+
+        None
+    }
+
+    /// Retrieves a mutable reference to a value by its `key`.
+    ///
+    /// The `key` may be any borrowed form of the key type, but `Hash` and `Eq` on the borrowed
+    /// form must match those of the key type.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(&mut value)`: If the key is found.
+    ///
+    /// - `None`: If the key does not exist.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.insert(1, "a");
+    ///
+    /// if let Some(value) = map.get_mut(&1) {
+    ///     *value = "b";
+    /// }
+    ///
+    /// assert_eq!(map.get(&1), Some(&"b"));
+    ///
+    /// // Key does not exist
+    /// assert_eq!(map.get_mut(&2), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        let hash = self.make_hash(key);
+
+        let result = self.find(hash, key);
+
+        if result.entry_exists() {
+            let value = unsafe { &mut self.entries.load_mut(result.entry).value };
+            return Some(value);
+        }
+
+        None
+    }
+
+    /// Retrieves mutable references to the values of multiple `keys` at once.
+    ///
+    /// The keys may be any borrowed form of the key type, but `Hash` and `Eq` on the borrowed
+    /// form must match those of the key type.
+    ///
+    /// # Returns
+    ///
+    /// - `Some([&mut value; N])`: If all the keys are found and they are distinct. The references
+    ///   are in the same order as the keys.
+    ///
+    /// - `None`: If any key does not exist, or if any key is given more than once.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(N²) in the number of keys, which is expected to be small.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    ///
+    /// if let Some([a, b]) = map.get_disjoint_mut([&"a", &"b"]) {
+    ///     core::mem::swap(a, b);
+    /// }
+    ///
+    /// assert_eq!(map.get(&"a"), Some(&2));
+    /// assert_eq!(map.get(&"b"), Some(&1));
+    ///
+    /// // Duplicate or missing keys.
+    /// assert!(map.get_disjoint_mut([&"a", &"a"]).is_none());
+    /// assert!(map.get_disjoint_mut([&"a", &"c"]).is_none());
+    /// ```
+    #[must_use]
+    pub fn get_disjoint_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        if N != 0 && self.is_empty() {
+            return None;
+        }
+
+        let mut indices = [0; N];
+
+        for (index, key) in indices.iter_mut().zip(keys) {
+            let hash = self.make_hash(key);
+
+            let result = self.find(hash, key);
+
+            if !result.entry_exists() {
+                return None;
+            }
+
+            *index = result.entry;
+        }
+
+        // Duplicate keys are resolved to the same index, which is rejected here.
+        let entries = self.get_disjoint_indices_mut(indices)?;
+
+        Some(entries.map(|(_, value)| value))
+    }
+
+    /// Returns the index of the entry with the specified `key`.
+    ///
+    /// The `key` may be any borrowed form of the key type, but `Hash` and `Eq` on the borrowed
+    /// form must match those of the key type.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(index)`: If the key is found.
+    ///
+    /// - `None`: If the key does not exist.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    ///
+    /// assert_eq!(map.get_index_of(&"b"), Some(1));
+    /// assert_eq!(map.get_index_of(&"c"), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        let hash = self.make_hash(key);
+
+        let result = self.find(hash, key);
+
+        if result.entry_exists() {
+            return Some(result.entry);
+        }
+
+        None
+    }
+
+    /// Retrieves the index, the key and the value of the entry with the specified `key`.
+    ///
+    /// The `key` may be any borrowed form of the key type, but `Hash` and `Eq` on the borrowed
+    /// form must match those of the key type.
+    ///
+    /// # Returns
+    ///
+    /// - `Some((index, &key, &value))`: If the key is found.
+    ///
+    /// - `None`: If the key does not exist.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    ///
+    /// assert_eq!(map.get_full(&"b"), Some((1, &"b", &2)));
+    /// assert_eq!(map.get_full(&"c"), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn get_full<Q>(&self, key: &Q) -> Option<(usize, &K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        let index = self.get_index_of(key)?;
+        let entry = unsafe { self.entries.load(index) };
+        Some((index, &entry.key, &entry.value))
+    }
+
+    /// Retrieves the stored key and the value of the entry with the specified `key`.
+    ///
+    /// This is useful when the stored key carries more information than the borrowed form used
+    /// for the lookup.
+    ///
+    /// # Returns
+    ///
+    /// - `Some((&key, &value))`: If the key is found.
+    ///
+    /// - `None`: If the key does not exist.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert(String::from("a"), 1);
+    ///
+    /// assert_eq!(map.get_key_value("a"), Some((&String::from("a"), &1)));
+    /// assert_eq!(map.get_key_value("b"), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        let index = self.get_index_of(key)?;
+        let entry = unsafe { self.entries.load(index) };
+        Some((&entry.key, &entry.value))
+    }
+
+    /// Returns the entry at the specified `index`.
+    ///
+    /// # Returns
+    ///
+    /// - `Some((&key, &value))`: If the index is within bounds.
+    ///
+    /// - `None`: If the index is out of bounds.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    ///
+    /// assert_eq!(map.get_index(1), Some((&"b", &2)));
+    /// assert_eq!(map.get_index(2), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        if index >= self.len {
+            return None;
+        }
+
+        let entry = unsafe { self.entries.load(index) };
+
+        Some((&entry.key, &entry.value))
+    }
+
+    /// Returns the entry at the specified `index` with a mutable reference to the value.
+    ///
+    /// The key is immutable, because changing it would invalidate the index.
+    ///
+    /// # Returns
+    ///
+    /// - `Some((&key, &mut value))`: If the index is within bounds.
+    ///
+    /// - `None`: If the index is out of bounds.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    ///
+    /// if let Some((_, value)) = map.get_index_mut(0) {
+    ///     *value = 10;
+    /// }
+    ///
+    /// assert_eq!(map.get(&"a"), Some(&10));
+    /// assert_eq!(map.get_index_mut(1), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        if index >= self.len {
+            return None;
+        }
+
+        let entry = unsafe { self.entries.load_mut(index) };
+
+        Some((&entry.key, &mut entry.value))
+    }
+
+    /// Returns the entries at multiple `indices` at once, with mutable references to the values.
+    ///
+    /// # Returns
+    ///
+    /// - `Some([(&key, &mut value); N])`: If all the indices are within bounds and they are
+    ///   distinct. The entries are in the same order as the indices.
+    ///
+    /// - `None`: If any index is out of bounds, or if any index is given more than once.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(N²) in the number of indices, which is expected to be small.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 3);
+    ///
+    /// if let Some([(_, a), (_, c)]) = map.get_disjoint_indices_mut([0, 2]) {
+    ///     *a += *c;
+    /// }
+    ///
+    /// assert_eq!(map.get(&"a"), Some(&4));
+    ///
+    /// assert!(map.get_disjoint_indices_mut([1, 1]).is_none());
+    /// assert!(map.get_disjoint_indices_mut([1, 3]).is_none());
+    /// ```
+    #[must_use]
+    pub fn get_disjoint_indices_mut<const N: usize>(
+        &mut self,
+        indices: [usize; N],
+    ) -> Option<[(&K, &mut V); N]> {
+        for (i, index) in indices.iter().enumerate() {
+            if *index >= self.len || indices[..i].contains(index) {
+                return None;
+            }
+        }
+
+        let entries = self.entries_slice_mut().as_mut_ptr();
+
+        // The indices are within bounds and distinct, so the references don't alias.
+        Some(array::from_fn(|i| unsafe {
+            let entry = &mut *entries.add(indices[i]);
+            (&entry.key, &mut entry.value)
+        }))
+    }
+
+    /// Returns the first entry in the map.
+    ///
+    /// # Returns
+    ///
+    /// - `Some((&key, &value))`: If the map is not empty.
+    ///
+    /// - `None`: If the map is empty.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    ///
+    /// assert_eq!(map.first(), Some((&1, &"a")));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn first(&self) -> Option<(&K, &V)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let entry = unsafe { self.entries.load_first() };
+
+        Some((&entry.key, &entry.value))
+    }
+
+    /// Returns the last entry in the map.
+    ///
+    /// # Returns
+    ///
+    /// - `Some((&key, &value))`: If the map is not empty.
+    ///
+    /// - `None`: If the map is empty.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    ///
+    /// assert_eq!(map.last(), Some((&3, &"c")));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn last(&self) -> Option<(&K, &V)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let entry = unsafe { self.entries.load(self.len - 1) };
+
+        Some((&entry.key, &entry.value))
+    }
+
+    /// Returns `true` if the map contains a value for the specified `key`.
+    ///
+    /// The `key` may be any borrowed form of the key type, but `Hash` and `Eq` on the borrowed
+    /// form must match those of the key type.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.insert(1, "a");
+    ///
+    /// // Key exists.
+    /// assert!(map.contains_key(&1));
+    ///
+    /// // Key does not exist.
+    /// assert!(!map.contains_key(&2));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Removes an entry by its `key` and returns its value.
+    ///
+    /// If `SHIFT` is `true`, this method will shift all entries after it to fill the gap, and
+    /// updates their slots.
+    ///
+    /// If `SHIFT` is `false`, this method will copy the last entry to the place of the removed
+    /// entry without shifting, and updates its slot.
+    ///
+    /// # Safety
+    ///
+    /// Map must not be empty when calling this method.
+    #[inline]
+    fn remove_entry<const SHIFT: bool, Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        let hash = self.make_hash(key);
+
+        let result = self.find(hash, key);
+
+        if result.entry_exists() {
+            let removed = unsafe { self.remove_found::<SHIFT>(result.slot, result.entry) };
+            return Some(removed.value);
+        }
+
+        // Key was not found.
+        None
+    }
+
+    /// Removes the entry at `index` which is referenced by the occupied `slot`, and returns it.
+    ///
+    /// The gap is filled according to `SHIFT` as described in [`OmniMap::remove_entry`].
+    ///
+    /// # Safety
+    ///
+    /// `slot` and `index` must be the result of a successful `find`, and no modifications to the
+    /// map must have been made in between.
+    pub(crate) unsafe fn remove_found<const SHIFT: bool>(
+        &mut self,
+        slot: usize,
+        index: usize,
+    ) -> Bucket<K, V> {
+        // Call order matters, the slot must be erased while the indexed entries are in place.
+        self.erase_slot(slot);
+
+        let last = self.len - 1;
+
+        if !SHIFT && likely(index != last) {
+            // The slot of the last entry is found by its cached hash and its index, without
+            // comparing the keys, so no user code runs while the map is being modified.
+            let swapped = self.find_slot_of(last);
+            self.index.store_entry_index(swapped, index);
+        }
+
+        self.len = last;
+
+        let removed = self.entries.read_for_ownership(index);
+
+        if likely(index != self.len) {
+            if SHIFT {
+                // Call order matters.
+                self.decrement_index(index, self.len);
+                self.entries.shift_left(index, self.len - index);
+            } else {
+                self.entries.memmove_one(self.len, index);
+            }
+        }
+
+        removed
+    }
+
+    /// Removes an entry by its `key`.
+    ///
+    /// If the removed entry is not the last one, this method shifts all elements after it to fill
+    /// the gap, which can be a significant performance overhead, especially with large number of
+    /// entries.
+    ///
+    /// If a strict order preservation is not required, consider using [`OmniMap::swap_remove`]
+    /// instead, which swaps the place of the last entry with the place of the removed entry to
+    /// fill the gap, without shifting.
+    ///
+    /// The `key` may be any borrowed form of the key type, but `Hash` and `Eq` on the borrowed
+    /// form must match those of the key type.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(value)`: If key's entry is found and removed.
+    ///
+    /// - `None`: If the key does not have entry.
+    ///
+    /// # Time Complexity
+    ///
+    /// - _O_(n) on average.
+    ///
+    /// - _O_(1) on average, if the key is of the last entry.
+    ///
+    /// # Note
+    /// This method does not shrink the current capacity of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    ///
+    /// assert_eq!(map.len(), 2);
+    ///
+    /// // Remove an existing key
+    /// assert_eq!(map.shift_remove(&1), Some("a"));
+    ///
+    /// assert_eq!(map.len(), 1);
+    ///
+    /// // Remove a non-existing key
+    /// assert_eq!(map.shift_remove(&1), None);
+    /// ```
+    pub fn shift_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        self.remove_entry::<true, Q>(key)
+    }
+
+    /// Removes an entry by its `key`, and swaps its place with the last entry.
+    ///
+    /// This method can be significantly faster than [`OmniMap::shift_remove`], if a strict order
+    /// preservation is not required.
+    ///
+    /// The `key` may be any borrowed form of the key type, but `Hash` and `Eq` on the borrowed
+    /// form must match those of the key type.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(value)`: If key's entry is found and removed.
+    ///
+    /// - `None`: If the key does not have entry.
+    ///
+    /// # Time Complexity
+    ///
+    /// - _O_(1) on average.
+    ///
+    /// # Note
+    /// This method does not shrink the current capacity of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    ///
+    /// assert_eq!(map.len(), 3);
+    ///
+    /// // Remove an existing key by swapping.
+    /// assert_eq!(map.swap_remove(&1), Some("a"));
+    ///
+    /// assert_eq!(map.len(), 2);
+    ///
+    /// // The last entry has been swapped, and it is accessible at the index of the removed entry.
+    /// assert_eq!(map[0], "c");
+    /// ```
+    pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        self.remove_entry::<false, Q>(key)
+    }
+
+    /// Pops the first entry from the map.
+    /// The capacity of the map remains unchanged.
+    ///
+    /// # Returns
+    ///
+    /// - `Some((key, value))`: If the map is not empty.
+    ///
+    /// - `None`: If the map is empty.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    ///
+    /// assert_eq!(map.pop_front(), Some((1, "a")));
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<(K, V)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        unsafe {
+            // The slot is found without comparing the keys, as in `remove_found`.
+            let slot = self.find_slot_of(0);
+            self.erase_slot(slot);
+
+            self.len -= 1;
+
+            let removed = self.entries.read_for_ownership(0);
+
+            // Call order matters.
+            self.decrement_index(0, self.len);
+            self.entries.shift_left(0, self.len);
+
+            Some((removed.key, removed.value))
+        }
+    }
+
+    /// Pops the last entry from the map.
+    /// The capacity of the map remains unchanged.
     ///
-    /// This method is no-op if `additional` is `0`.
+    /// # Returns
     ///
-    /// # Panics
+    /// - `Some((key, value))`: If the map is not empty.
     ///
-    /// This method will panic if capacity overflow occurs, or when allocation fails.
+    /// - `None`: If the map is empty.
     ///
     /// # Time Complexity
     ///
-    /// _O_(n) on average.
+    /// _O_(1) on average.
     ///
     /// # Examples
     ///
@@ -562,121 +2737,76 @@ where
     /// use omnimap::OmniMap;
     ///
     /// let mut map = OmniMap::new();
-    ///
-    /// // The allocated capacity with first insert is 4.
     /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
     ///
-    /// // Reserve space for 10 more elements
-    /// map.reserve(10);
-    ///
-    /// // The capacity is now 14
-    /// assert_eq!(map.capacity(), 14);
+    /// assert_eq!(map.pop(), Some((3, "c")));
+    /// assert_eq!(map.len(), 2);
     /// ```
-    #[inline]
-    pub fn reserve(&mut self, additional: usize) {
-        match self.reserve_additional(additional, OnError::NoReturn) {
-            Ok(_) => (),
-            // Hints the compiler that the error branch can be eliminated from the call chain.
-            Err(_) => unsafe { unreachable_unchecked() },
-        };
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        unsafe {
+            // The slot is found without comparing the keys, as in `remove_found`.
+            let slot = self.find_slot_of(self.len - 1);
+            self.erase_slot(slot);
+
+            self.len -= 1;
+
+            let removed = self.entries.read_for_ownership(self.len);
+
+            Some((removed.key, removed.value))
+        }
     }
 
-    /// Tries to reserve capacity for `additional` elements in advance.
+    /// Moves the entry at the index `from` to the index `to`, and shifts the entries in between.
     ///
-    /// This method is semantically equivalent to [`OmniMap::reserve`], except that it returns an
-    /// error instead of panicking when the allocation fails.
+    /// The relative order of all other entries is preserved.
     ///
-    /// The resulting capacity will be equal to `self.capacity() + additional` or _more_ to
-    /// maintain the load factor.
+    /// # Panics
     ///
-    /// This method is no-op if `additional` is `0`.
+    /// If `from` or `to` is out of bounds.
     ///
     /// # Time Complexity
     ///
-    /// _O_(n) on average.
+    /// _O_(n) where `n` is the distance between `from` and `to`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use omnimap::{AllocError, OmniMap};
+    /// use omnimap::OmniMap;
     ///
     /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 3);
     ///
-    /// // The allocated capacity with first insert is 4.
-    /// map.insert(1, "a");
-    ///
-    /// // Try reserve space for very large number of elements.
-    /// let mut result = map.try_reserve(usize::MAX);
-    ///
-    /// // Result must be error.
-    /// assert!(matches!(result.err().unwrap(), AllocError::Overflow));
-    ///
-    /// // The capacity remains 3
-    /// assert_eq!(map.capacity(), 3);
-    ///
-    /// // Reserve space for 10 more elements
-    /// result = map.try_reserve(10);
-    ///
-    /// assert!(result.is_ok());
+    /// map.move_index(0, 2);
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&"b", &"c", &"a"]);
     ///
-    /// // The capacity is now 14
-    /// assert_eq!(map.capacity(), 14);
+    /// map.move_index(2, 1);
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&"b", &"a", &"c"]);
     /// ```
     #[inline]
-    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
-        self.reserve_additional(additional, OnError::ReturnErr)
-    }
-
-    /// Finds the slot of the key in the index.
-    ///
-    /// If the key already exists, the returned `slot` is the index of its slot and `entry`
-    /// is the index of its entry.
-    ///
-    /// If the key doesn't exist, the retuned `slot` is free for inserting and the value
-    /// of `entry` is an **invalid** index.
-    ///
-    /// # Safety
-    ///
-    /// Before using `entry`, its value must be checked first with `entry_exists()` method,
-    /// because its value can be an invalid index.
-    fn find(&self, hash: usize, key: &K) -> FindResult {
-        unsafe {
-            let mut slot = hash % self.cap;
-            // For all valid models: (empty slots exist) -> (unbounded loop can't be infinite).
-            loop {
-                match self.index.read_tag(slot) {
-                    Tag::Empty => return FindResult::just_slot(slot),
-                    Tag::Occupied => {
-                        let entry = self.index.read_entry_index(slot);
-                        if self.entries.load(entry).key == *key {
-                            return FindResult { slot, entry };
-                        }
-                    }
-                    Tag::Deleted => {}
-                }
-
-                slot = (slot + 1) % self.cap;
-            }
-        }
+    pub fn move_index(&mut self, from: usize, to: usize) {
+        assert!(from < self.len && to < self.len, "Index out of bounds.");
+        unsafe { self.move_index_unchecked(from, to) }
     }
 
-    /// Inserts a key-value pair into the map.
-    /// If the map did not have this key present, `None` is returned.
-    /// If the map did have this key present, the value is updated, and the old value is returned.
-    ///
-    /// # Parameters
-    ///
-    /// - `key`: The key to insert or update.
+    /// Swaps the positions of the entries at the indices `a` and `b`.
     ///
-    /// - `value`: The value to associate with the key.
+    /// The positions of all other entries are not affected.
     ///
     /// # Panics
     ///
-    /// This method will panic if capacity overflow occurs, or when allocation fails.
+    /// If `a` or `b` is out of bounds.
     ///
     /// # Time Complexity
     ///
-    /// _O_(1) Amortized.
+    /// _O_(1) on average.
     ///
     /// # Examples
     ///
@@ -684,66 +2814,40 @@ where
     /// use omnimap::OmniMap;
     ///
     /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 3);
     ///
-    ///  // When inserting a new key-value pair, None is returned
-    ///  map.insert(1, "a");
-    ///  map.insert(2, "b");
-    ///
-    /// assert_eq!(map.get(&1), Some(&"a"));
-    /// assert_eq!(map.get(&2), Some(&"b"));
-    ///
-    /// // Update the value for an existing key
-    /// let old_value = map.insert(1, "c");
-    ///
-    /// // The old value is returned
-    /// assert_eq!(old_value, Some("a"));
+    /// map.swap_indices(0, 2);
     ///
-    /// // The value is updated
-    /// assert_eq!(map.get(&1), Some(&"c"));
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&"c", &"b", &"a"]);
+    /// assert_eq!(map.get_index_of(&"a"), Some(2));
     /// ```
     #[inline]
-    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
-        if unlikely(self.len + self.deleted == self.capacity()) {
-            self.reclaim_or_reserve();
-        }
-
-        let hash = Self::make_hash(&key);
+    pub fn swap_indices(&mut self, a: usize, b: usize) {
+        assert!(a < self.len && b < self.len, "Index out of bounds.");
 
-        let result = self.find(hash, &key);
-        
-        if result.entry_exists() {
-            let entry = unsafe { self.entries.load_mut(result.entry) };
-            let old_val = mem::replace(&mut entry.value, value);
-            return Some(old_val);
-        };
+        if a == b {
+            return;
+        }
 
         unsafe {
-            debug_assert!(
-                self.index.read_tag(result.slot).is_empty(),
-                "Logic error: attempt to overwrite a non-empty slot while inserting"
-            );
-
-            self.index.store(result.slot, Tag::Occupied, self.len);
-            self.entries.store(self.len, Entry::new(key, value, hash));
-        }
+            // Both slots must be found before updating any of them.
+            let slot_a = self.find_slot_of(a);
+            let slot_b = self.find_slot_of(b);
 
-        self.len += 1;
+            self.index.store_entry_index(slot_a, b);
+            self.index.store_entry_index(slot_b, a);
 
-        // Key was new and inserted.
-        None
+            self.entries.as_slice_mut(self.len).swap(a, b);
+        }
     }
 
-    /// Retrieves a value by its `key`.
-    ///
-    /// # Returns
-    ///
-    /// - `Some(&value)`: if the key is found.
-    ///
-    /// - `None`: if the key does not exist.
+    /// Reverses the order of the entries in place.
     ///
     /// # Time Complexity
     ///
-    /// _O_(1) on average.
+    /// _O_(n) where `n` is the allocated capacity.
     ///
     /// # Examples
     ///
@@ -751,44 +2855,62 @@ where
     /// use omnimap::OmniMap;
     ///
     /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 3);
     ///
-    ///  map.insert(1, "a");
-    ///
-    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// map.reverse();
     ///
-    /// // Key does not exist
-    /// assert_eq!(map.get(&2), None);
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&"c", &"b", &"a"]);
+    /// assert_eq!(map.get_index_of(&"a"), Some(2));
     /// ```
-    #[must_use]
-    #[inline]
-    pub fn get(&self, key: &K) -> Option<&V> {
-        if self.is_empty() {
-            return None;
+    pub fn reverse(&mut self) {
+        if self.len < 2 {
+            return;
         }
 
-        let hash = Self::make_hash(key);
+        let last = self.len - 1;
 
-        let result = self.find(hash, key);
+        unsafe {
+            // Every occupied slot references an entry, so all of them are affected.
+            let mut i = 0;
+            while i < self.cap {
+                if self.index.read_tag(i).is_occupied() {
+                    let index = self.index.entry_index_ref_mut(i);
+                    *index = last - *index;
+                }
+                i += 1;
+            }
 
-        if result.entry_exists() {
-            let value = unsafe { &self.entries.load(result.entry).value };
-            return Some(value);
+            self.entries.as_slice_mut(self.len).reverse();
         }
-
-        None
     }
 
-    /// Retrieves a mutable reference to a value by its `key`.
-    ///
-    /// # Returns
+    /// Sorts the entries in place with the function `apply`, and rebuilds the index once.
     ///
-    /// - `Some(&mut value)`: If the key is found.
+    /// The cached hashes of the entries are reused, so no key is hashed again.
+    #[inline]
+    fn sort_with<F>(&mut self, apply: F)
+    where
+        F: FnOnce(&mut [Bucket<K, V>]),
+    {
+        if self.len < 2 {
+            return;
+        }
+
+        // Rebuilds the index even if `apply` panics, the entries remain a permutation.
+        let guard = ReindexGuard { map: self };
+        apply(unsafe { guard.map.entries.as_slice_mut(guard.map.len) });
+    }
+
+    /// Sorts the entries of the map by their keys.
     ///
-    /// - `None`: If the key does not exist.
+    /// The sort is stable, and the index is rebuilt once after sorting without rehashing the
+    /// keys.
     ///
     /// # Time Complexity
     ///
-    /// _O_(1) on average.
+    /// _O_(n log n + m) where `n` is the length and `m` is the allocated capacity.
     ///
     /// # Examples
     ///
@@ -796,48 +2918,33 @@ where
     /// use omnimap::OmniMap;
     ///
     /// let mut map = OmniMap::new();
+    /// map.insert("c", 3);
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
     ///
-    /// map.insert(1, "a");
-    ///
-    /// if let Some(value) = map.get_mut(&1) {
-    ///     *value = "b";
-    /// }
-    ///
-    /// assert_eq!(map.get(&1), Some(&"b"));
+    /// map.sort_keys();
     ///
-    /// // Key does not exist
-    /// assert_eq!(map.get_mut(&2), None);
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&"a", &"b", &"c"]);
+    /// assert_eq!(map.get_index_of(&"c"), Some(2));
     /// ```
-    #[must_use]
-    #[inline]
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
-        if self.is_empty() {
-            return None;
-        }
-
-        let hash = Self::make_hash(key);
-
-        let result = self.find(hash, key);
-
-        if result.entry_exists() {
-            let value = unsafe { &mut self.entries.load_mut(result.entry).value };
-            return Some(value);
-        }
-
-        None
+    pub fn sort_keys(&mut self)
+    where
+        K: Ord,
+    {
+        self.sort_with(|entries| entries.sort_by(|a, b| a.key.cmp(&b.key)));
     }
 
-    /// Returns the first entry in the map.
-    ///
-    /// # Returns
+    /// Sorts the entries of the map with the comparator function `compare`.
     ///
-    /// - `Some((&key, &value))`: If the map is not empty.
+    /// The comparator receives the key and the value of two entries. The sort is stable, and the
+    /// index is rebuilt once after sorting without rehashing the keys.
     ///
-    /// - `None`: If the map is empty.
+    /// If `compare` panics, the entries remain in an unspecified order, but the map remains
+    /// consistent.
     ///
     /// # Time Complexity
     ///
-    /// _O_(1).
+    /// _O_(n log n + m) where `n` is the length and `m` is the allocated capacity.
     ///
     /// # Examples
     ///
@@ -845,35 +2952,35 @@ where
     /// use omnimap::OmniMap;
     ///
     /// let mut map = OmniMap::new();
-    /// map.insert(1, "a");
-    /// map.insert(2, "b");
-    /// map.insert(3, "c");
+    /// map.insert("a", 3);
+    /// map.insert("b", 1);
+    /// map.insert("c", 2);
     ///
-    /// assert_eq!(map.first(), Some((&1, &"a")));
+    /// map.sort_by(|_, v1, _, v2| v1.cmp(v2));
+    ///
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&"b", &"c", &"a"]);
     /// ```
-    #[must_use]
-    #[inline]
-    pub const fn first(&self) -> Option<(&K, &V)> {
-        if self.is_empty() {
-            return None;
-        }
-
-        let entry = unsafe { self.entries.load_first() };
-
-        Some((&entry.key, &entry.value))
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&K, &V, &K, &V) -> Ordering,
+    {
+        self.sort_with(|entries| {
+            entries.sort_by(|a, b| compare(&a.key, &a.value, &b.key, &b.value))
+        });
     }
 
-    /// Returns the last entry in the map.
+    /// Sorts the entries of the map with the comparator function `compare`, but might not
+    /// preserve the order of equal entries.
     ///
-    /// # Returns
-    ///
-    /// - `Some((&key, &value))`: If the map is not empty.
+    /// The comparator receives the key and the value of two entries. The index is rebuilt once
+    /// after sorting without rehashing the keys.
     ///
-    /// - `None`: If the map is empty.
+    /// If `compare` panics, the entries remain in an unspecified order, but the map remains
+    /// consistent.
     ///
     /// # Time Complexity
     ///
-    /// _O_(1).
+    /// _O_(n log n + m) where `n` is the length and `m` is the allocated capacity.
     ///
     /// # Examples
     ///
@@ -881,29 +2988,72 @@ where
     /// use omnimap::OmniMap;
     ///
     /// let mut map = OmniMap::new();
+    /// map.insert(3, "c");
     /// map.insert(1, "a");
     /// map.insert(2, "b");
-    /// map.insert(3, "c");
     ///
-    /// assert_eq!(map.last(), Some((&3, &"c")));
+    /// map.sort_unstable_by(|k1, _, k2, _| k2.cmp(k1));
+    ///
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&3, &2, &1]);
     /// ```
-    #[must_use]
-    #[inline]
-    pub const fn last(&self) -> Option<(&K, &V)> {
-        if self.is_empty() {
-            return None;
-        }
-
-        let entry = unsafe { self.entries.load(self.len - 1) };
+    pub fn sort_unstable_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&K, &V, &K, &V) -> Ordering,
+    {
+        self.sort_with(|entries| {
+            entries.sort_unstable_by(|a, b| compare(&a.key, &a.value, &b.key, &b.value))
+        });
+    }
 
-        Some((&entry.key, &entry.value))
+    /// Sorts the entries of the map by the keys extracted with the function `extract`.
+    ///
+    /// The function `extract` is called only once per entry, which is useful when extracting the
+    /// sort key is expensive. The sort is stable, and the index is rebuilt once after sorting
+    /// without rehashing the keys of the map.
+    ///
+    /// If `extract` panics, the entries remain in an unspecified order, but the map remains
+    /// consistent.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n log n + m) where `n` is the length and `m` is the allocated capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert("a", -3);
+    /// map.insert("b", 1);
+    /// map.insert("c", -2);
+    ///
+    /// map.sort_by_cached_key(|_, v| v.to_string());
+    ///
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&"c", &"a", &"b"]);
+    /// ```
+    pub fn sort_by_cached_key<T, F>(&mut self, mut extract: F)
+    where
+        T: Ord,
+        F: FnMut(&K, &V) -> T,
+    {
+        self.sort_with(|entries| {
+            entries.sort_by_cached_key(|entry| extract(&entry.key, &entry.value))
+        });
     }
 
-    /// Returns `true` if the map contains a value for the specified `key`.
+    /// Retains only the entries for which the predicate `keep` returns `true`, and removes the
+    /// rest.
+    ///
+    /// The order of the retained entries is preserved. The entries are compacted in a single
+    /// pass, and the index is rebuilt once, only if any entry has been removed.
+    ///
+    /// If `keep` panics, the entries that have been removed remain removed, the rest are
+    /// retained, and the map remains consistent.
     ///
     /// # Time Complexity
     ///
-    /// _O_(1) on average.
+    /// _O_(n + m) where `n` is the length and `m` is the allocated capacity.
     ///
     /// # Examples
     ///
@@ -912,92 +3062,164 @@ where
     ///
     /// let mut map = OmniMap::new();
     ///
-    /// map.insert(1, "a");
+    /// for i in 0..6 {
+    ///     map.insert(i, i * 10);
+    /// }
     ///
-    /// // Key exists.
-    /// assert!(map.contains_key(&1));
+    /// map.retain(|key, _| key % 2 == 0);
     ///
-    /// // Key does not exist.
-    /// assert!(!map.contains_key(&2));
+    /// assert_eq!(map.iter_keys().collect::<Vec<_>>(), vec![&0, &2, &4]);
+    /// assert_eq!(map.get_index_of(&4), Some(2));
     /// ```
-    #[must_use]
     #[inline]
-    pub fn contains_key(&self, key: &K) -> bool {
-        self.get(key).is_some()
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.retain_mut(|key, value| keep(key, value));
     }
 
-    /// Removes an entry by its `key` and returns its value.
+    /// Retains only the entries for which the predicate `keep` returns `true`, and removes the
+    /// rest.
     ///
-    /// If `SHIFT` is `true`, this method will shift all entries after it to fill the gab, and
-    /// updates their slots.
+    /// Unlike [`OmniMap::retain`], the predicate receives a mutable reference to the value, so
+    /// the retained values can be modified in the same pass.
     ///
-    /// If `SHIFT` is `false`, this method will copy the last entry to the place of the removed
-    /// entry without shifting, and updates its slot.
+    /// If `keep` panics, the entries that have been removed remain removed, the rest are
+    /// retained, and the map remains consistent.
     ///
-    /// # Safety
+    /// # Time Complexity
     ///
-    /// Map must not be empty when calling this method.
-    #[inline]
-    fn remove_entry<const SHIFT: bool>(&mut self, key: &K) -> Option<V> {
-        let hash = Self::make_hash(key);
+    /// _O_(n + m) where `n` is the length and `m` is the allocated capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// for i in 0..6 {
+    ///     map.insert(i, i * 10);
+    /// }
+    ///
+    /// map.retain_mut(|key, value| {
+    ///     *value += 1;
+    ///     key % 2 == 1
+    /// });
+    ///
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&1, &11), (&3, &31), (&5, &51)]);
+    /// ```
+    pub fn retain_mut<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        if self.len == 0 {
+            return;
+        }
 
-        let result = self.find(hash, key);
-        
-        if result.entry_exists() {
-            let index = result.entry;
+        let original_len = self.len;
 
-            self.len -= 1;
-            self.deleted += 1;
+        // The entries are not accessible through the map while processing.
+        self.len = 0;
+
+        let mut guard = RetainGuard {
+            map: self,
+            processed: 0,
+            removed: 0,
+            original_len,
+        };
 
+        while guard.processed < original_len {
             unsafe {
-                let removed = self.entries.read_for_ownership(index).value;
-                self.index.store_tag(result.slot, Tag::Deleted);
-
-                if likely(index != self.len) {
-                    if SHIFT {
-                        // Call order matters.
-                        self.decrement_index(index, self.len);
-                        self.entries.shift_left(index, self.len - index);
-                    } else {
-                        let last = self.entries.load(self.len);
-                        let swapped = self.find(last.hash, &last.key);
-                        self.index.store_entry_index(swapped.slot, index);
-                        self.entries.memmove_one(self.len, index);
+                let entry = guard.map.entries.load_mut(guard.processed);
+
+                if keep(&entry.key, &mut entry.value) {
+                    if guard.removed > 0 {
+                        guard
+                            .map
+                            .entries
+                            .memmove_one(guard.processed, guard.processed - guard.removed);
                     }
+                    guard.processed += 1;
+                } else {
+                    // Counted before dropping, the guard must not touch this entry again.
+                    guard.processed += 1;
+                    guard.removed += 1;
+                    ptr::drop_in_place(entry);
                 }
-
-                return Some(removed);
-            };
+            }
         }
-
-        // Key was not found.
-        None
     }
 
-    /// Removes an entry by its `key`.
+    /// Removes the entries in the specified `range` from the map and returns them as an iterator,
+    /// preserving the order of the remaining entries.
     ///
-    /// If the removed entry is not the last one, this method shifts all elements after it to fill
-    /// the gab, which can be a significant performance overhead, especially with large number of
-    /// entries.
+    /// The range is removed even if the iterator is not fully consumed. If the iterator is leaked,
+    /// e.g. with [`mem::forget`], the map is left with the entries before the range only.
     ///
-    /// If a strict order preservation is not required, consider using [`OmniMap::swap_remove`]
-    /// instead, which swaps the place of the last entry with the place of the removed entry to
-    /// fill the gab, without shifting.
+    /// # Panics
     ///
-    /// # Returns
+    /// This method will panic if the start of the range is greater than its end, or if the end of
+    /// the range is greater than the length of the map.
     ///
-    /// - `Some(value)`: If key's entry is found and removed.
+    /// # Time Complexity
     ///
-    /// - `None`: If the key does not have entry.
+    /// _O_(n) where `n` is the number of the entries from the start of the range to the end of the
+    /// map.
     ///
-    /// # Time Complexity
+    /// # Examples
     ///
-    /// - _O_(n) on average.
+    /// ```
+    /// use omnimap::OmniMap;
     ///
-    /// - _O_(1) on average, if the key is of the last entry.
+    /// let mut map = OmniMap::new();
     ///
-    /// # Note
-    /// This method does not shrink the current capacity of the map.
+    /// for i in 0..5 {
+    ///     map.insert(i, i * 10);
+    /// }
+    ///
+    /// let drained: Vec<_> = map.drain(1..3).collect();
+    ///
+    /// assert_eq!(drained, vec![(1, 10), (2, 20)]);
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&0, &0), (&3, &30), (&4, &40)]);
+    /// assert_eq!(map.get(&3), Some(&30));
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, K, V, S, A>
+    where
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = bounds_of(range, self.len);
+        let tail_len = self.len - end;
+
+        if start < end {
+            // The drained entries and the tail are not accessible through the map while draining,
+            // and they are removed from the index in advance, so leaking the iterator leaves the
+            // map in a consistent state. The entries before the range keep their slots.
+            unsafe { self.unindex_tail(start) };
+            self.len = start;
+        }
+
+        Drain {
+            map: self,
+            start,
+            offset: start,
+            end,
+            tail: end,
+            tail_len,
+        }
+    }
+
+    /// Returns an iterator that removes and yields the entries for which the predicate `extract`
+    /// returns `true`, preserving the order of the remaining entries.
+    ///
+    /// The entries are removed only as the iterator advances. If the iterator is dropped before
+    /// being fully consumed, the entries that have not been visited are retained. If the iterator
+    /// is leaked, e.g. with [`mem::forget`], the map is left empty.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n + m) where `n` is the length and `m` is the allocated capacity.
     ///
     /// # Examples
     ///
@@ -1006,43 +3228,65 @@ where
     ///
     /// let mut map = OmniMap::new();
     ///
-    /// map.insert(1, "a");
-    /// map.insert(2, "b");
-    ///
-    /// assert_eq!(map.len(), 2);
-    ///
-    /// // Remove an existing key
-    /// assert_eq!(map.shift_remove(&1), Some("a"));
+    /// for i in 0..6 {
+    ///     map.insert(i, i * 10);
+    /// }
     ///
-    /// assert_eq!(map.len(), 1);
+    /// let extracted: Vec<_> = map.extract_if(|key, _| key % 2 == 0).collect();
     ///
-    /// // Remove a non-existing key
-    /// assert_eq!(map.shift_remove(&1), None);
+    /// assert_eq!(extracted, vec![(0, 0), (2, 20), (4, 40)]);
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&1, &10), (&3, &30), (&5, &50)]);
+    /// assert_eq!(map.get(&5), Some(&50));
     /// ```
-    pub fn shift_remove(&mut self, key: &K) -> Option<V> {
-        if self.is_empty() {
-            return None;
+    pub fn extract_if<F>(&mut self, extract: F) -> ExtractIf<'_, K, V, F, S, A>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let original_len = self.len;
+
+        // The entries are not accessible through the map while extracting. The index is cleared
+        // in advance and rebuilt when the iterator is dropped, so leaking the iterator leaves the
+        // map empty but consistent.
+        self.len = 0;
+        if self.cap != 0 {
+            unsafe { self.index.set_tags_empty(self.cap) };
+        }
+
+        ExtractIf {
+            map: self,
+            extract,
+            processed: 0,
+            removed: 0,
+            original_len,
         }
-        self.remove_entry::<true>(key)
     }
 
-    /// Removes an entry by its `key`, and swaps its place with the last entry.
+    /// Removes the entries starting from `from` from the index.
     ///
-    /// This method can be significantly faster than [`OmniMap::shift_remove`], if a strict order
-    /// preservation is not required.
+    /// The slots of the removed entries are erased, the entries themselves are not touched and the
+    /// length is not changed.
     ///
-    /// # Returns
+    /// # Safety
     ///
-    /// - `Some(value)`: If key's entry is found and removed.
+    /// `from` must be less than or equal to the length.
+    unsafe fn unindex_tail(&mut self, from: usize) {
+        debug_assert!(from <= self.len);
+
+        for i in from..self.len {
+            let slot = self.find_slot_of(i);
+            self.erase_slot(slot);
+        }
+    }
+
+    /// Shortens the map, keeping the first `len` entries and dropping the rest.
     ///
-    /// - `None`: If the key does not have entry.
+    /// This method is no-op if `len` is greater than or equal to the current length.
     ///
-    /// # Time Complexity
+    /// The allocated capacity is not changed.
     ///
-    /// - _O_(1) on average.
+    /// # Time Complexity
     ///
-    /// # Note
-    /// This method does not shrink the current capacity of the map.
+    /// _O_(n) where `n` is the number of the dropped entries.
     ///
     /// # Examples
     ///
@@ -1051,39 +3295,52 @@ where
     ///
     /// let mut map = OmniMap::new();
     ///
-    /// map.insert(1, "a");
-    /// map.insert(2, "b");
-    /// map.insert(3, "c");
-    ///
-    /// assert_eq!(map.len(), 3);
-    ///
-    /// // Remove an existing key by swapping.
-    /// assert_eq!(map.swap_remove(&1), Some("a"));
+    /// for i in 0..5 {
+    ///     map.insert(i, i * 10);
+    /// }
     ///
-    /// assert_eq!(map.len(), 2);
+    /// map.truncate(2);
     ///
-    /// // The last entry has been swapped, and it is accessible at the index of the removed entry.
-    /// assert_eq!(map[0], "c");
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&0, &0), (&1, &10)]);
+    /// assert_eq!(map.get(&3), None);
     /// ```
-    pub fn swap_remove(&mut self, key: &K) -> Option<V> {
-        if self.is_empty() {
-            return None;
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        if len == 0 {
+            self.clear();
+            return;
+        }
+
+        unsafe {
+            self.unindex_tail(len);
+
+            let end = self.len;
+
+            // The length is set before dropping, so the map remains consistent if a destructor
+            // panics.
+            self.len = len;
+            self.entries.drop_range(len..end);
         }
-        self.remove_entry::<false>(key)
     }
 
-    /// Pops the first entry from the map.
-    /// The capacity of the map remains unchanged.
+    /// Splits the map into two at the given index.
     ///
-    /// # Returns
+    /// Returns a new map containing the entries in the range `[at, len)`, using clones of the
+    /// hasher and the allocator of this map. This map is left with the entries in the range
+    /// `[0, at)`.
     ///
-    /// - `Some((key, value))`: If the map is not empty.
+    /// The order of the entries is preserved in both maps, and the keys are not rehashed.
     ///
-    /// - `None`: If the map is empty.
+    /// # Panics
+    ///
+    /// This method will panic if `at` is greater than the length, or when allocation fails.
     ///
     /// # Time Complexity
     ///
-    /// _O_(n).
+    /// _O_(n) where `n` is the number of the moved entries.
     ///
     /// # Examples
     ///
@@ -1091,55 +3348,66 @@ where
     /// use omnimap::OmniMap;
     ///
     /// let mut map = OmniMap::new();
-    /// map.insert(1, "a");
-    /// map.insert(2, "b");
-    /// map.insert(3, "c");
     ///
-    /// assert_eq!(map.pop_front(), Some((1, "a")));
-    /// assert_eq!(map.len(), 2);
+    /// for i in 0..5 {
+    ///     map.insert(i, i * 10);
+    /// }
+    ///
+    /// let other = map.split_off(3);
+    ///
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&0, &0), (&1, &10), (&2, &20)]);
+    /// assert_eq!(other.iter().collect::<Vec<_>>(), vec![(&3, &30), (&4, &40)]);
+    /// assert_eq!(other.get(&4), Some(&40));
     /// ```
-    pub fn pop_front(&mut self) -> Option<(K, V)> {
-        if self.is_empty() {
-            return None;
-        }
-
-        // SAFETY: The map is not empty, so an entry must exist.
-        let entry_ref = unsafe { self.entries.load_first() };
+    #[must_use]
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        S: Clone,
+        A: Clone,
+    {
+        assert!(at <= self.len, "Index out of bounds.");
 
-        let result = self.find(entry_ref.hash, &entry_ref.key);
+        let count = self.len - at;
 
-        debug_assert!(
-            result.entry_exists(),
-            "Logic error: entry exists, but it has no associated slot in the index"
-        );
+        let mut other =
+            Self::with_capacity_and_hasher_in(count, self.hash_builder.clone(), self.alloc.clone());
 
-        self.len -= 1;
-        self.deleted += 1;
+        if count == 0 {
+            return other;
+        }
 
         unsafe {
-            let removed = self.entries.read_for_ownership(0);
-            self.index.store_tag(result.slot, Tag::Deleted);
+            self.unindex_tail(at);
 
-            // Call order matters.
-            self.decrement_index(0, self.len);
-            self.entries.shift_left(0, self.len);
+            ptr::copy_nonoverlapping(
+                self.entries.access().add(at),
+                other.entries.access_mut_as::<Bucket<K, V>>(),
+                count,
+            );
 
-            Some((removed.key, removed.value))
+            self.len = at;
+            other.len = count;
+
+            // The hash builder is a clone, so the cached hashes are still valid.
+            other.build_index();
         }
+
+        other
     }
 
-    /// Pops the last entry from the map.
-    /// The capacity of the map remains unchanged.
+    /// Moves all the entries of `other` into this map, leaving `other` empty.
     ///
-    /// # Returns
+    /// The entries of `other` are inserted in their order. If a key already exists in this map,
+    /// its value is replaced by the value from `other` and it keeps its current position, like
+    /// [`OmniMap::insert`]. The capacity of `other` is not changed.
     ///
-    /// - `Some((key, value))`: If the map is not empty.
+    /// # Panics
     ///
-    /// - `None`: If the map is empty.
+    /// This method will panic if capacity overflow occurs, or when allocation fails.
     ///
     /// # Time Complexity
     ///
-    /// _O_(1) on average.
+    /// _O_(n) on average, where `n` is the length of `other`.
     ///
     /// # Examples
     ///
@@ -1149,33 +3417,21 @@ where
     /// let mut map = OmniMap::new();
     /// map.insert(1, "a");
     /// map.insert(2, "b");
-    /// map.insert(3, "c");
     ///
-    /// assert_eq!(map.pop(), Some((3, "c")));
-    /// assert_eq!(map.len(), 2);
+    /// let mut other = OmniMap::new();
+    /// other.insert(3, "c");
+    /// other.insert(1, "x");
+    ///
+    /// map.append(&mut other);
+    ///
+    /// assert!(other.is_empty());
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&1, &"x"), (&2, &"b"), (&3, &"c")]);
     /// ```
-    pub fn pop(&mut self) -> Option<(K, V)> {
-        if self.is_empty() {
-            return None;
-        }
-
-        let entry_ref = unsafe { self.entries.load(self.len - 1) };
+    pub fn append(&mut self, other: &mut Self) {
+        self.reserve_free(other.len);
 
-        let result = self.find(entry_ref.hash, &entry_ref.key);
-
-        debug_assert!(
-            result.entry_exists(),
-            "Logic error: entry exists, but it has no associated slot in the index"
-        );
-
-        self.len -= 1;
-        self.deleted += 1;
-
-        unsafe {
-            let removed = self.entries.read_for_ownership(self.len);
-            self.index.store_tag(result.slot, Tag::Deleted);
-
-            Some((removed.key, removed.value))
+        for (key, value) in other.drain(..) {
+            self.insert(key, value);
         }
     }
 
@@ -1210,7 +3466,7 @@ where
         if likely(capacity >= self.len && capacity < self.capacity()) {
             if likely(self.len > 0) {
                 // Shrink and keep reserves.
-                let new_cap = Self::allocation_capacity_unchecked(capacity);
+                let new_cap = allocation_capacity_unchecked(capacity);
                 match self.reallocate_reindex(new_cap, OnError::NoReturn) {
                     Ok(_) => (),
                     Err(_) => unsafe { unreachable_unchecked() },
@@ -1251,7 +3507,7 @@ where
         if likely(self.capacity() > self.len) {
             if likely(self.len > 0) {
                 // Shrink and keep reserves.
-                let new_cap = Self::allocation_capacity_unchecked(self.len);
+                let new_cap = allocation_capacity_unchecked(self.len);
                 match self.reallocate_reindex(new_cap, OnError::NoReturn) {
                     Ok(_) => (),
                     Err(_) => unsafe { unreachable_unchecked() },
@@ -1296,34 +3552,52 @@ where
         }
 
         self.len = 0;
-        self.deleted = 0;
     }
 
-    /// Returns an iterator over the current entries.
+    /// Returns an iterator over the entries in the `OmniMap`.
     ///
-    /// This method makes it safe to iterate over the entries without worrying about the state of
-    /// the pointer and to trick the compiler to return empty iterator without type inference
-    /// issues when used with `map`.
-    fn iter_entries(&self) -> Iter<Entry<K, V>> {
-        if self.len == 0 {
-            return [].iter();
-        };
-        unsafe { self.entries.as_slice(self.len).iter() }
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    ///
+    /// assert_eq!(map.iter().collect::<Vec<(&i32, &&str)>>(), vec![(&1, &"a"), (&2, &"b")]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.entries_slice())
     }
 
     /// Returns a mutable iterator over the entries in the `OmniMap`.
     ///
-    /// This method makes it safe to iterate over the entries without worrying about the state of
-    /// the pointer and to trick the compiler to return empty iterator without type inference
-    /// issues when used with `map`.
-    fn iter_entries_mut(&mut self) -> IterMut<'_, Entry<K, V>> {
-        if self.len == 0 {
-            return [].iter_mut();
-        };
-        unsafe { self.entries.as_slice_mut(self.len).iter_mut() }
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    ///
+    /// for (key, value) in map.iter_mut() {
+    ///     *value = "c";
+    /// }
+    ///
+    /// assert_eq!(map.get(&1), Some(&"c"));
+    /// assert_eq!(map.get(&2), Some(&"c"));
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(self.entries_slice_mut())
     }
 
-    /// Returns an iterator over the entries in the `OmniMap`.
+    /// Returns an iterator over the keys in the `OmniMap`.
     ///
     /// # Examples
     ///
@@ -1335,14 +3609,14 @@ where
     /// map.insert(1, "a");
     /// map.insert(2, "b");
     ///
-    /// assert_eq!(map.iter().collect::<Vec<(&i32, &&str)>>(), vec![(&1, &"a"), (&2, &"b")]);
+    /// assert_eq!(map.iter_keys().collect::<Vec<&i32>>(), vec![&1, &2]);
     /// ```
     #[inline]
-    pub fn iter(&self) -> EntriesIterator<'_, K, V> {
-        self.iter_entries().map(|entry| (&entry.key, &entry.value))
+    pub fn iter_keys(&self) -> Keys<'_, K, V> {
+        Keys::new(self.entries_slice())
     }
 
-    /// Returns a mutable iterator over the entries in the `OmniMap`.
+    /// Returns an iterator over the values in the `OmniMap`.
     ///
     /// # Examples
     ///
@@ -1354,20 +3628,37 @@ where
     /// map.insert(1, "a");
     /// map.insert(2, "b");
     ///
-    /// for (key, value) in map.iter_mut() {
-    ///     *value = "c";
+    /// assert_eq!(map.iter_values().collect::<Vec<&&str>>(), vec![&"a", &"b"]);
+    /// ```
+    #[inline]
+    pub fn iter_values(&self) -> Values<'_, K, V> {
+        Values::new(self.entries_slice())
+    }
+
+    /// Returns an iterator over mutable references to the values in the `OmniMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    ///
+    /// for value in map.values_mut() {
+    ///     *value += 1;
     /// }
     ///
-    /// assert_eq!(map.get(&1), Some(&"c"));
-    /// assert_eq!(map.get(&2), Some(&"c"));
+    /// assert_eq!(map.iter_values().collect::<Vec<&i32>>(), vec![&11, &21]);
     /// ```
     #[inline]
-    pub fn iter_mut(&mut self) -> EntriesIteratorMut<'_, K, V> {
-        self.iter_entries_mut()
-            .map(|entry| (&entry.key, &mut entry.value))
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(self.entries_slice_mut())
     }
 
-    /// Returns an iterator over the keys in the `OmniMap`.
+    /// Consumes the `OmniMap` and returns an iterator over its keys.
     ///
     /// # Examples
     ///
@@ -1379,14 +3670,14 @@ where
     /// map.insert(1, "a");
     /// map.insert(2, "b");
     ///
-    /// assert_eq!(map.iter_keys().collect::<Vec<&i32>>(), vec![&1, &2]);
+    /// assert_eq!(map.into_keys().rev().collect::<Vec<i32>>(), vec![2, 1]);
     /// ```
     #[inline]
-    pub fn iter_keys(&self) -> impl Iterator<Item = &K> {
-        self.iter_entries().map(|entry| &entry.key)
+    pub fn into_keys(self) -> IntoKeys<K, V, A> {
+        IntoKeys::new(self.into_iter())
     }
 
-    /// Returns an iterator over the values in the `OmniMap`.
+    /// Consumes the `OmniMap` and returns an iterator over its values.
     ///
     /// # Examples
     ///
@@ -1398,11 +3689,11 @@ where
     /// map.insert(1, "a");
     /// map.insert(2, "b");
     ///
-    /// assert_eq!(map.iter_values().collect::<Vec<&&str>>(), vec![&"a", &"b"]);
+    /// assert_eq!(map.into_values().collect::<Vec<&str>>(), vec!["a", "b"]);
     /// ```
     #[inline]
-    pub fn iter_values(&self) -> impl Iterator<Item = &V> {
-        self.iter_entries().map(|entry| &entry.value)
+    pub fn into_values(self) -> IntoValues<K, V, A> {
+        IntoValues::new(self.into_iter())
     }
 
     /// Returns the current load factor.
@@ -1414,12 +3705,12 @@ where
         if self.cap == 0 {
             0.0
         } else {
-            (self.len + self.deleted) as f64 / self.cap as f64
+            self.len as f64 / self.cap as f64
         }
     }
 }
 
-impl<K, V> Drop for OmniMap<K, V> {
+impl<K, V, S, A: Allocator> Drop for OmniMap<K, V, S, A> {
     fn drop(&mut self) {
         if self.cap == 0 {
             return;
@@ -1430,17 +3721,20 @@ impl<K, V> Drop for OmniMap<K, V> {
             self.entries.drop_initialized(self.len);
             // Infallible, uncontrolled. Already allocated.
             let layout = self.entries.make_layout_unchecked(self.cap);
-            self.entries.deallocate(layout);
-            self.index.deallocate(self.cap);
+            self.entries.deallocate(&self.alloc, layout);
+            self.index.deallocate(&self.alloc, self.cap);
         }
     }
 }
 
-impl<K, V> Default for OmniMap<K, V>
+impl<K, V, S, A> Default for OmniMap<K, V, S, A>
 where
     K: Eq + Hash,
+    S: BuildHasher + Default,
+    A: Allocator + Default,
 {
-    /// Creates a new `OmniMap` with the default capacity.
+    /// Creates a new `OmniMap` with the default capacity, the default hash builder and the
+    /// default allocator.
     /// The default capacity is set to `16`.
     ///
     /// # Panics
@@ -1458,11 +3752,134 @@ where
     /// ```
     #[inline]
     fn default() -> Self {
-        Self::with_capacity(Self::DEFAULT_CAPACITY)
+        Self::with_capacity_and_hasher_in(Self::DEFAULT_CAPACITY, S::default(), A::default())
+    }
+}
+
+impl<K, V, S, A> FromIterator<(K, V)> for OmniMap<K, V, S, A>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+    A: Allocator + Default,
+{
+    /// Creates a new `OmniMap` from the key-value pairs of `iter`, with the default hash builder
+    /// and the default allocator.
+    ///
+    /// The capacity is reserved in advance according to the lower bound of the size hint of
+    /// `iter`. If a key occurs more than once, the last value is kept at the position of the
+    /// first occurrence.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if capacity overflow occurs, or when allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let map: OmniMap<_, _> = [(1, "a"), (2, "b"), (1, "c")].into_iter().collect();
+    ///
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&1, &"c"), (&2, &"b")]);
+    /// ```
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::with_hasher_in(S::default(), A::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S, A> Extend<(K, V)> for OmniMap<K, V, S, A>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Inserts the key-value pairs of `iter` into the map in their order.
+    ///
+    /// The capacity is reserved in advance according to the lower bound of the size hint of
+    /// `iter`. If a key already exists, its value is replaced and it keeps its current position.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if capacity overflow occurs, or when allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert(1, "a");
+    ///
+    /// map.extend([(2, "b"), (1, "c")]);
+    ///
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&1, &"c"), (&2, &"b")]);
+    /// ```
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+
+        // If the map is not empty, some keys might already exist, so only half of the hint is
+        // reserved to avoid over-allocation. The map grows as needed anyway.
+        let hint = iter.size_hint().0;
+        let additional = if self.len == 0 {
+            hint
+        } else {
+            hint.div_ceil(2)
+        };
+        self.reserve_free(additional);
+
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V, S, A> Extend<(&'a K, &'a V)> for OmniMap<K, V, S, A>
+where
+    K: Eq + Hash + Copy,
+    V: Copy,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Inserts copies of the key-value pairs of `iter` into the map in their order.
+    ///
+    /// This method has the same semantics as the implementation for owned pairs.
+    #[inline]
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, const N: usize> From<[(K, V); N]> for OmniMap<K, V>
+where
+    K: Eq + Hash,
+{
+    /// Creates a new `OmniMap` from the key-value pairs of `array`, with the default hash
+    /// builder.
+    ///
+    /// If a key occurs more than once, the last value is kept at the position of the first
+    /// occurrence.
+    ///
+    /// This function requires the feature `std`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let map = OmniMap::from([(1, "a"), (2, "b")]);
+    ///
+    /// assert_eq!(map.get(&2), Some(&"b"));
+    /// ```
+    #[inline]
+    fn from(array: [(K, V); N]) -> Self {
+        Self::from_iter(array)
     }
 }
 
-impl<K, V> Index<usize> for OmniMap<K, V> {
+impl<K, V, S, A: Allocator> Index<usize> for OmniMap<K, V, S, A> {
     type Output = V;
 
     /// Returns immutable reference to the value at the specified `index`.
@@ -1490,7 +3907,7 @@ impl<K, V> Index<usize> for OmniMap<K, V> {
     }
 }
 
-impl<K, V> IndexMut<usize> for OmniMap<K, V> {
+impl<K, V, S, A: Allocator> IndexMut<usize> for OmniMap<K, V, S, A> {
     /// Returns mutable reference to the value at the specified `index`.
     ///
     /// # Panics
@@ -1519,12 +3936,14 @@ impl<K, V> IndexMut<usize> for OmniMap<K, V> {
     }
 }
 
-impl<'a, K, V> IntoIterator for &'a OmniMap<K, V>
+impl<'a, K, V, S, A> IntoIterator for &'a OmniMap<K, V, S, A>
 where
     K: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
 {
     type Item = (&'a K, &'a V);
-    type IntoIter = EntriesIterator<'a, K, V>;
+    type IntoIter = Iter<'a, K, V>;
 
     /// Returns an iterator over the entries.
     fn into_iter(self) -> Self::IntoIter {
@@ -1532,12 +3951,14 @@ where
     }
 }
 
-impl<'a, K, V> IntoIterator for &'a mut OmniMap<K, V>
+impl<'a, K, V, S, A> IntoIterator for &'a mut OmniMap<K, V, S, A>
 where
     K: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
 {
     type Item = (&'a K, &'a mut V);
-    type IntoIter = EntriesIteratorMut<'a, K, V>;
+    type IntoIter = IterMut<'a, K, V>;
 
     /// Returns a mutable iterator over the entries.
     fn into_iter(self) -> Self::IntoIter {
@@ -1545,10 +3966,12 @@ where
     }
 }
 
-impl<K, V> PartialEq for OmniMap<K, V>
+impl<K, V, S, A> PartialEq for OmniMap<K, V, S, A>
 where
     K: Eq + Hash,
     V: PartialEq,
+    S: BuildHasher,
+    A: Allocator,
 {
     fn eq(&self, other: &Self) -> bool {
         if self.len() != other.len() {
@@ -1559,23 +3982,115 @@ where
     }
 }
 
-impl<K, V> OmniMap<K, V>
+impl<K, V, S, A> Eq for OmniMap<K, V, S, A>
+where
+    K: Eq + Hash,
+    V: Eq,
+    S: BuildHasher,
+    A: Allocator,
+{
+}
+
+impl<K, V, S, A> OmniMap<K, V, S, A>
+where
+    K: PartialEq,
+    V: PartialEq,
+    A: Allocator,
+{
+    /// Compares the entries of both maps in order.
+    ///
+    /// Unlike `==`, which ignores the order of the entries, two maps are equal only if they have
+    /// equal entries at the same positions. The hash builders don't have to be equal.
+    ///
+    /// If both maps use the default [`RandomState`](crate::RandomState) and their hash builders
+    /// are equal, as for clones and maps created with the same seed, the cached hashes of the
+    /// keys are compared first, so most of the different keys are rejected without comparing
+    /// them.
+    ///
+    /// To use the order-sensitive equality as `PartialEq`, see [`OrderedMap`](crate::OrderedMap).
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let mut a = OmniMap::with_hasher(RandomState::new());
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    ///
+    /// let mut b = a.clone();
+    /// b.reverse();
+    ///
+    /// assert!(a == b);
+    /// assert!(!a.eq_ordered(&b));
+    ///
+    /// b.reverse();
+    /// assert!(a.eq_ordered(&b));
+    /// ```
+    pub fn eq_ordered(&self, other: &Self) -> bool
+    where
+        S: 'static,
+    {
+        if self.len != other.len {
+            return false;
+        }
+
+        // The hashes are comparable only if they are produced by the same hash function.
+        let compare_hashes = self.has_same_hash_builder(other);
+
+        self.entries_slice()
+            .iter()
+            .zip(other.entries_slice())
+            .all(|(a, b)| {
+                (!compare_hashes || a.hash == b.hash) && a.key == b.key && a.value == b.value
+            })
+    }
+
+    /// Returns `true` if both maps are known to hash the keys in the same way.
+    ///
+    /// `S` is not required to implement `PartialEq`, so only the hash builders of type
+    /// [`RandomState`] are compared. For any other type, `false` is returned.
+    fn has_same_hash_builder(&self, other: &Self) -> bool
+    where
+        S: 'static,
+    {
+        let this: &dyn Any = &self.hash_builder;
+        let other: &dyn Any = &other.hash_builder;
+
+        match (
+            this.downcast_ref::<RandomState>(),
+            other.downcast_ref::<RandomState>(),
+        ) {
+            (Some(this), Some(other)) => this == other,
+            _ => false,
+        }
+    }
+}
+
+impl<K, V, S, A> OmniMap<K, V, S, A>
 where
     K: Eq + Hash + Clone,
     V: Clone,
+    S: BuildHasher + Clone,
+    A: Allocator + Clone,
 {
     /// Makes new clone from the current instance with two modes: compact and normal.
     ///
     /// The map must be allocated and not empty before calling this method.
-    fn make_clone<const COMPACT: bool>(&self) -> OmniMap<K, V> {
+    fn make_clone<const COMPACT: bool>(&self) -> Self {
         let cap = if COMPACT {
             // len + required reserves.
-            Self::allocation_capacity_unchecked(self.len)
+            allocation_capacity_unchecked(self.len)
         } else {
             self.cap
         };
 
-        let mut instance = Self::new();
+        let mut instance = Self::with_hasher_in(self.hash_builder.clone(), self.alloc.clone());
 
         match instance.allocate::<COMPACT>(cap, OnError::NoReturn) {
             Ok(_) => {
@@ -1585,10 +4100,8 @@ where
                     instance.entries.clone_from(self.entries.access(), self.len);
                     instance.len = self.len;
                     if COMPACT {
-                        instance.deleted = 0;
                         instance.build_index();
                     } else {
-                        instance.deleted = self.deleted;
                         instance.index.copy_from(&self.index, self.cap);
                     }
                 }
@@ -1629,16 +4142,18 @@ where
     #[inline]
     pub fn clone_compact(&self) -> Self {
         if self.is_empty() {
-            return Self::new();
+            return Self::with_hasher_in(self.hash_builder.clone(), self.alloc.clone());
         }
         self.make_clone::<true>()
     }
 }
 
-impl<K, V> Clone for OmniMap<K, V>
+impl<K, V, S, A> Clone for OmniMap<K, V, S, A>
 where
     K: Eq + Hash + Clone,
     V: Clone,
+    S: BuildHasher + Clone,
+    A: Allocator + Clone,
 {
     /// Creates an identical clone of the current instance without changing the capacity.
     /// The new map will have the same capacity as the original regardless of the number of
@@ -1654,21 +4169,39 @@ where
     fn clone(&self) -> Self {
         // Return an unallocated instance if the original is unallocated.
         if self.cap == 0 {
-            return Self::new();
+            return Self::with_hasher_in(self.hash_builder.clone(), self.alloc.clone());
         }
         self.make_clone::<false>()
     }
 }
 
 /// An owning iterator over the entries of the map.
-pub struct OmniMapIterator<K, V> {
-    entries: UnsafeBufferPointer<Entry<K, V>>,
+pub struct OmniMapIterator<K, V, A: Allocator = Global> {
+    entries: UnsafeBufferPointer<Bucket<K, V>>,
     cap: usize,
     offset: usize,
     end: usize,
+    alloc: A,
+}
+
+// The iterator owns the remaining entries exclusively, like the map it was created from.
+unsafe impl<K, V, A> Send for OmniMapIterator<K, V, A>
+where
+    K: Send,
+    V: Send,
+    A: Allocator + Send,
+{
+}
+
+unsafe impl<K, V, A> Sync for OmniMapIterator<K, V, A>
+where
+    K: Sync,
+    V: Sync,
+    A: Allocator + Sync,
+{
 }
 
-impl<K, V> Iterator for OmniMapIterator<K, V> {
+impl<K, V, A: Allocator> Iterator for OmniMapIterator<K, V, A> {
     type Item = (K, V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -1696,9 +4229,62 @@ impl<K, V> Iterator for OmniMapIterator<K, V> {
         let len = self.len();
         (len, Some(len))
     }
+
+    /// Drops the next `n` entries and returns the entry after them.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let skip = n.min(self.len());
+        if skip != 0 {
+            let start = self.offset;
+            // Advanced before dropping, so the skipped entries are not dropped again if a
+            // destructor panics.
+            self.offset += skip;
+            unsafe { self.entries.drop_range(start..self.offset) };
+        }
+        self.next()
+    }
+
+    /// Drops the remaining entries except the last one and returns it.
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline(always)]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V, A: Allocator> DoubleEndedIterator for OmniMapIterator<K, V, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.offset < self.end {
+            self.end -= 1;
+            let entry = unsafe {
+                // Note: The destructor of the iterator must not call drop on this value,
+                // or it will be double-drop.
+                self.entries.read_for_ownership(self.end)
+            };
+            Some((entry.key, entry.value))
+        } else {
+            None
+        }
+    }
+
+    /// Drops the last `n` entries and returns the entry before them.
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let skip = n.min(self.len());
+        if skip != 0 {
+            let end = self.end;
+            // Moved back before dropping, so the skipped entries are not dropped again if a
+            // destructor panics.
+            self.end -= skip;
+            unsafe { self.entries.drop_range(self.end..end) };
+        }
+        self.next_back()
+    }
 }
 
-impl<K, V> ExactSizeIterator for OmniMapIterator<K, V> {
+impl<K, V, A: Allocator> ExactSizeIterator for OmniMapIterator<K, V, A> {
     /// Returns the number of remaining entries in the iterator.
     #[inline(always)]
     fn len(&self) -> usize {
@@ -1706,7 +4292,9 @@ impl<K, V> ExactSizeIterator for OmniMapIterator<K, V> {
     }
 }
 
-impl<K, V> Drop for OmniMapIterator<K, V> {
+impl<K, V, A: Allocator> FusedIterator for OmniMapIterator<K, V, A> {}
+
+impl<K, V, A: Allocator> Drop for OmniMapIterator<K, V, A> {
     fn drop(&mut self) {
         if self.entries.is_null() {
             return;
@@ -1721,14 +4309,14 @@ impl<K, V> Drop for OmniMapIterator<K, V> {
 
             // Infallible, uncontrolled. Already allocated.
             let layout = self.entries.make_layout_unchecked(self.cap);
-            self.entries.deallocate(layout);
+            self.entries.deallocate(&self.alloc, layout);
         }
     }
 }
 
-impl<K, V> IntoIterator for OmniMap<K, V> {
+impl<K, V, S, A: Allocator> IntoIterator for OmniMap<K, V, S, A> {
     type Item = (K, V);
-    type IntoIter = OmniMapIterator<K, V>;
+    type IntoIter = OmniMapIterator<K, V, A>;
 
     /// Consumes the `OmniMap` and returns an iterator over its entries.
     ///
@@ -1749,52 +4337,223 @@ impl<K, V> IntoIterator for OmniMap<K, V> {
     /// assert_eq!(iter.next(), None);
     /// ```
     fn into_iter(self) -> Self::IntoIter {
-        let mut iterator = OmniMapIterator {
-            entries: UnsafeBufferPointer::new(),
-            cap: 0,
-            end: 0,
-            offset: 0,
-        };
-
-        if self.len == 0 {
-            return iterator;
-        }
-
-        iterator.cap = self.cap;
-        iterator.end = self.len;
-
         let mut manual_self = ManuallyDrop::new(self);
+        let this = &mut *manual_self;
 
         // The fields that need deallocation are index and entries.
         // index must be deallocated here and entries shall be deallocated by the iterator.
+        // The hash builder is no longer needed, so it is dropped here, and the allocator is moved
+        // to the iterator.
         unsafe {
-            manual_self.index.deallocate(iterator.cap);
-            iterator.entries = manual_self.entries.invalidate();
+            if this.cap != 0 {
+                this.index.deallocate(&this.alloc, this.cap);
+            }
+
+            ptr::drop_in_place(&mut this.hash_builder);
+
+            OmniMapIterator {
+                entries: this.entries.invalidate(),
+                cap: this.cap,
+                offset: 0,
+                end: this.len,
+                alloc: ptr::read(&this.alloc),
+            }
+        }
+    }
+}
+
+/// A draining iterator over a range of entries of the map.
+///
+/// This struct is created by [`OmniMap::drain`].
+pub struct Drain<'a, K, V, S = DefaultHashBuilder, A: Allocator = Global> {
+    map: &'a mut OmniMap<K, V, S, A>,
+    start: usize,
+    offset: usize,
+    end: usize,
+    tail: usize,
+    tail_len: usize,
+}
+
+impl<K, V, S, A: Allocator> Iterator for Drain<'_, K, V, S, A> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset < self.end {
+            let entry = unsafe {
+                // Note: The destructor of the iterator must not call drop on this value,
+                // or it will be double-drop.
+                self.map.entries.read_for_ownership(self.offset)
+            };
+            self.offset += 1;
+            Some((entry.key, entry.value))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of remaining entries in the iterator.
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<K, V, S, A: Allocator> DoubleEndedIterator for Drain<'_, K, V, S, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.offset < self.end {
+            self.end -= 1;
+            let entry = unsafe {
+                // Note: The destructor of the iterator must not call drop on this value,
+                // or it will be double-drop.
+                self.map.entries.read_for_ownership(self.end)
+            };
+            Some((entry.key, entry.value))
+        } else {
+            None
+        }
+    }
+}
+
+impl<K, V, S, A: Allocator> ExactSizeIterator for Drain<'_, K, V, S, A> {
+    /// Returns the number of remaining entries in the iterator.
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.end - self.offset
+    }
+}
+
+impl<K, V, S, A: Allocator> FusedIterator for Drain<'_, K, V, S, A> {}
+
+impl<K, V, S, A: Allocator> Drop for Drain<'_, K, V, S, A> {
+    fn drop(&mut self) {
+        // Nothing has been removed, the map and the index are untouched.
+        if self.start == self.tail {
+            return;
+        }
+
+        let remaining = self.offset..self.end;
+        self.offset = self.end;
+
+        let guard = DrainTailGuard { drain: self };
+
+        if !remaining.is_empty() {
+            // Drop the remaining entries.
+            unsafe { guard.drain.map.entries.drop_range(remaining) };
+        }
+    }
+}
+
+/// An iterator that removes and yields the entries of the map that match a predicate.
+///
+/// This struct is created by [`OmniMap::extract_if`].
+pub struct ExtractIf<'a, K, V, F, S = DefaultHashBuilder, A: Allocator = Global>
+where
+    F: FnMut(&K, &mut V) -> bool,
+{
+    map: &'a mut OmniMap<K, V, S, A>,
+    extract: F,
+    processed: usize,
+    removed: usize,
+    original_len: usize,
+}
+
+impl<K, V, F, S, A> Iterator for ExtractIf<'_, K, V, F, S, A>
+where
+    F: FnMut(&K, &mut V) -> bool,
+    A: Allocator,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.processed < self.original_len {
+            let current = self.processed;
+            unsafe {
+                let entry = self.map.entries.load_mut(current);
+                let extract = (self.extract)(&entry.key, &mut entry.value);
+
+                // Counted after the predicate returns, so the entry is retained if it panics.
+                self.processed += 1;
+
+                if extract {
+                    self.removed += 1;
+                    // Note: The destructor of the iterator must not call drop on this value,
+                    // or it will be double-drop.
+                    let entry = self.map.entries.read_for_ownership(current);
+                    return Some((entry.key, entry.value));
+                }
+
+                if self.removed > 0 {
+                    self.map
+                        .entries
+                        .memmove_one(current, current - self.removed);
+                }
+            }
+        }
+        None
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.original_len - self.processed))
+    }
+}
+
+// Once all entries are processed, `processed == original_len` and the iterator stays exhausted.
+impl<K, V, F, S, A> FusedIterator for ExtractIf<'_, K, V, F, S, A>
+where
+    F: FnMut(&K, &mut V) -> bool,
+    A: Allocator,
+{
+}
+
+impl<K, V, F, S, A> Drop for ExtractIf<'_, K, V, F, S, A>
+where
+    F: FnMut(&K, &mut V) -> bool,
+    A: Allocator,
+{
+    fn drop(&mut self) {
+        if self.removed > 0 {
+            unsafe {
+                // No-op if all entries have been processed.
+                self.map.entries.memmove(
+                    self.processed,
+                    self.processed - self.removed,
+                    self.original_len - self.processed,
+                );
+            }
         }
 
-        iterator
+        self.map.len = self.original_len - self.removed;
+
+        // The index has been cleared when the iterator was created.
+        self.map.build_index();
     }
 }
 
-impl<K, V> Debug for OmniMap<K, V>
+impl<K, V, S, A> Debug for OmniMap<K, V, S, A>
 where
     K: Eq + Hash + Debug,
     V: Debug,
+    S: BuildHasher,
+    A: Allocator,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
     }
 }
 
-impl<K, V> Display for OmniMap<K, V>
+impl<K, V, S, A> Display for OmniMap<K, V, S, A>
 where
     K: Display + Eq + Hash,
     V: Display,
+    S: BuildHasher,
+    A: Allocator,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "{{")?;
         // This call is safe even if the map is not allocated.
-        for entry in self.iter_entries() {
+        for entry in self.entries_slice() {
             writeln!(f, "    {}: {}", entry.key, entry.value)?;
         }
         write!(f, "}}")
@@ -1802,8 +4561,8 @@ where
 }
 
 /// Development and testing methods that are not available in release builds.
-#[cfg(test)]
-impl<K, V> OmniMap<K, V> {
+#[cfg(all(test, feature = "std"))]
+impl<K, V, S, A: Allocator> OmniMap<K, V, S, A> {
     /// Returns the tag's value of the slot at the specified `offset`.
     ///
     /// This method is used for testing purposes only and not available in release builds.
@@ -1820,11 +4579,13 @@ impl<K, V> OmniMap<K, V> {
         unsafe { self.index.read_entry_index(offset) }
     }
 
-    /// Returns the number of deleted slots.
+    /// Returns the number of occupied slots in the index.
     ///
     /// This method is used for testing purposes only and not available in release builds.
-    pub(crate) fn debug_deleted(&self) -> usize {
-        self.deleted
+    pub(crate) fn debug_occupied(&self) -> usize {
+        (0..self.cap)
+            .filter(|&i| unsafe { self.index.read_tag(i) }.is_occupied())
+            .count()
     }
 
     /// Returns the number of allocated slots/entries.
@@ -0,0 +1,173 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::Debug;
+use core::hash::{BuildHasher, Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+
+use crate::alloc::{Allocator, Global};
+use crate::hash::DefaultHashBuilder;
+use crate::map::OmniMap;
+
+/// A wrapper of [`OmniMap`] with order-sensitive comparison and hashing.
+///
+/// The equality of [`OmniMap`] ignores the order of the entries. This wrapper compares the
+/// entries of both maps in order instead, and implements `Hash`, `PartialOrd` and `Ord` by the
+/// lexicographic order of the entries. It can be used as a key in another map, or to
+/// deduplicate maps by their structure.
+///
+/// The wrapper dereferences to the inner map.
+///
+/// # Examples
+///
+/// ```
+/// use omnimap::{OmniMap, OrderedMap};
+///
+/// let a: OmniMap<i32, i32> = [(1, 10), (2, 20)].into();
+/// let b: OmniMap<i32, i32> = [(2, 20), (1, 10)].into();
+///
+/// // The maps are equal regardless of the order.
+/// assert_eq!(a, b);
+///
+/// let a = OrderedMap::new(a);
+/// let b = OrderedMap::new(b);
+///
+/// // But the ordered wrappers are not.
+/// assert_ne!(a, b);
+/// assert!(a < b);
+///
+/// let mut seen = OmniMap::new();
+/// seen.insert(a, "first");
+/// seen.insert(b, "second");
+///
+/// assert_eq!(seen.len(), 2);
+/// ```
+pub struct OrderedMap<K, V, S = DefaultHashBuilder, A: Allocator = Global> {
+    map: OmniMap<K, V, S, A>,
+}
+
+impl<K, V, S, A: Allocator> OrderedMap<K, V, S, A> {
+    /// Wraps `map` to compare and hash it in order.
+    #[must_use]
+    #[inline(always)]
+    pub const fn new(map: OmniMap<K, V, S, A>) -> Self {
+        Self { map }
+    }
+
+    /// Returns the inner map.
+    #[must_use]
+    #[inline]
+    pub fn into_inner(self) -> OmniMap<K, V, S, A> {
+        self.map
+    }
+}
+
+impl<K, V, S, A: Allocator> From<OmniMap<K, V, S, A>> for OrderedMap<K, V, S, A> {
+    #[inline(always)]
+    fn from(map: OmniMap<K, V, S, A>) -> Self {
+        Self::new(map)
+    }
+}
+
+impl<K, V, S, A: Allocator> Deref for OrderedMap<K, V, S, A> {
+    type Target = OmniMap<K, V, S, A>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<K, V, S, A: Allocator> DerefMut for OrderedMap<K, V, S, A> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.map
+    }
+}
+
+impl<K, V, S, A> Clone for OrderedMap<K, V, S, A>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+    A: Allocator + Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.map.clone())
+    }
+}
+
+impl<K, V, S, A> PartialEq for OrderedMap<K, V, S, A>
+where
+    K: PartialEq,
+    V: PartialEq,
+    S: 'static,
+    A: Allocator,
+{
+    /// Compares the entries of both maps in order with [`OmniMap::eq_ordered`].
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.map.eq_ordered(&other.map)
+    }
+}
+
+impl<K, V, S, A> Eq for OrderedMap<K, V, S, A>
+where
+    K: Eq,
+    V: Eq,
+    S: 'static,
+    A: Allocator,
+{
+}
+
+impl<K, V, S, A> PartialOrd for OrderedMap<K, V, S, A>
+where
+    K: PartialOrd,
+    V: PartialOrd,
+    S: 'static,
+    A: Allocator,
+{
+    /// Compares the entries of both maps lexicographically.
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.map.as_slice().partial_cmp(other.map.as_slice())
+    }
+}
+
+impl<K, V, S, A> Ord for OrderedMap<K, V, S, A>
+where
+    K: Ord,
+    V: Ord,
+    S: 'static,
+    A: Allocator,
+{
+    /// Compares the entries of both maps lexicographically.
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.map.as_slice().cmp(other.map.as_slice())
+    }
+}
+
+impl<K, V, S, A> Hash for OrderedMap<K, V, S, A>
+where
+    K: Hash,
+    V: Hash,
+    A: Allocator,
+{
+    /// Hashes the entries of the map in order.
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.map.as_slice().hash(state);
+    }
+}
+
+impl<K, V, S, A> Debug for OrderedMap<K, V, S, A>
+where
+    K: Debug,
+    V: Debug,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.as_slice().fmt(f)
+    }
+}
@@ -0,0 +1,317 @@
+use alloc_crate::vec::Vec;
+use core::hash::{BuildHasher, Hash};
+
+use rayon::iter::plumbing::{Consumer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{
+    FromParallelIterator, IndexedParallelIterator, IntoParallelIterator, ParallelExtend,
+    ParallelIterator,
+};
+use rayon::{slice, vec};
+
+use crate::alloc::Allocator;
+use crate::map::{Bucket, OmniMap};
+
+/// A parallel iterator over the entries of a map.
+///
+/// This struct is created by [`OmniMap::par_iter`].
+pub struct ParIter<'a, K, V> {
+    inner: slice::Iter<'a, Bucket<K, V>>,
+}
+
+/// A parallel iterator over the entries of a map with mutable references to the values.
+///
+/// This struct is created by [`OmniMap::par_iter_mut`].
+pub struct ParIterMut<'a, K, V> {
+    inner: slice::IterMut<'a, Bucket<K, V>>,
+}
+
+/// A parallel iterator over the keys of a map.
+///
+/// This struct is created by [`OmniMap::par_keys`].
+pub struct ParKeys<'a, K, V> {
+    inner: slice::Iter<'a, Bucket<K, V>>,
+}
+
+/// A parallel iterator over the values of a map.
+///
+/// This struct is created by [`OmniMap::par_values`].
+pub struct ParValues<'a, K, V> {
+    inner: slice::Iter<'a, Bucket<K, V>>,
+}
+
+/// An owning parallel iterator over the entries of a map.
+///
+/// This struct is created by the `into_par_iter` method of the map.
+pub struct IntoParIter<K, V> {
+    inner: vec::IntoIter<(K, V)>,
+}
+
+// The parallel iterators split by position, so they forward to the inner indexed parallel
+// iterator and map the yielded item.
+macro_rules! impl_par_iterator {
+    ([$($generics:tt)*] $ty:ty, $item:ty, |$entry:ident| $map:expr) => {
+        impl<$($generics)*> ParallelIterator for $ty {
+            type Item = $item;
+
+            #[inline]
+            fn drive_unindexed<C>(self, consumer: C) -> C::Result
+            where
+                C: UnindexedConsumer<Self::Item>,
+            {
+                self.inner.map(|$entry| $map).drive_unindexed(consumer)
+            }
+
+            #[inline(always)]
+            fn opt_len(&self) -> Option<usize> {
+                Some(self.inner.len())
+            }
+        }
+
+        impl<$($generics)*> IndexedParallelIterator for $ty {
+            #[inline]
+            fn drive<C>(self, consumer: C) -> C::Result
+            where
+                C: Consumer<Self::Item>,
+            {
+                self.inner.map(|$entry| $map).drive(consumer)
+            }
+
+            #[inline(always)]
+            fn len(&self) -> usize {
+                self.inner.len()
+            }
+
+            #[inline]
+            fn with_producer<CB>(self, callback: CB) -> CB::Output
+            where
+                CB: ProducerCallback<Self::Item>,
+            {
+                self.inner.map(|$entry| $map).with_producer(callback)
+            }
+        }
+    };
+}
+
+impl_par_iterator!(['a, K: Sync, V: Sync] ParIter<'a, K, V>, (&'a K, &'a V), |entry| (&entry.key, &entry.value));
+impl_par_iterator!(['a, K: Send + Sync, V: Send] ParIterMut<'a, K, V>, (&'a K, &'a mut V), |entry| (&entry.key, &mut entry.value));
+impl_par_iterator!(['a, K: Sync, V: Sync] ParKeys<'a, K, V>, &'a K, |entry| &entry.key);
+impl_par_iterator!(['a, K: Sync, V: Sync] ParValues<'a, K, V>, &'a V, |entry| &entry.value);
+impl_par_iterator!([K: Send, V: Send] IntoParIter<K, V>, (K, V), |entry| entry);
+
+impl<K, V, S, A: Allocator> OmniMap<K, V, S, A> {
+    /// Returns a parallel iterator over the entries of the map.
+    ///
+    /// The iterator is indexed, and the entries are split by their position in the map.
+    ///
+    /// This function requires the feature `rayon`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    /// use rayon::prelude::*;
+    ///
+    /// let map: OmniMap<u32, u32> = (0..100).map(|i| (i, i * 2)).collect();
+    ///
+    /// let sum: u32 = map.par_iter().map(|(_, value)| value).sum();
+    ///
+    /// assert_eq!(sum, 9900);
+    /// ```
+    #[inline]
+    pub fn par_iter(&self) -> ParIter<'_, K, V>
+    where
+        K: Sync,
+        V: Sync,
+    {
+        ParIter {
+            inner: self.entries_slice().into_par_iter(),
+        }
+    }
+
+    /// Returns a parallel iterator over the entries of the map with mutable references to the
+    /// values.
+    ///
+    /// This function requires the feature `rayon`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut map: OmniMap<u32, u32> = (0..100).map(|i| (i, i)).collect();
+    ///
+    /// map.par_iter_mut().for_each(|(key, value)| *value += key);
+    ///
+    /// assert_eq!(map.get(&10), Some(&20));
+    /// ```
+    #[inline]
+    pub fn par_iter_mut(&mut self) -> ParIterMut<'_, K, V>
+    where
+        K: Send + Sync,
+        V: Send,
+    {
+        ParIterMut {
+            inner: self.entries_slice_mut().into_par_iter(),
+        }
+    }
+
+    /// Returns a parallel iterator over the keys of the map.
+    ///
+    /// This function requires the feature `rayon`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    /// use rayon::prelude::*;
+    ///
+    /// let map: OmniMap<u32, u32> = (0..100).map(|i| (i, i)).collect();
+    ///
+    /// let keys: Vec<&u32> = map.par_keys().collect();
+    ///
+    /// assert_eq!(keys, map.iter_keys().collect::<Vec<_>>());
+    /// ```
+    #[inline]
+    pub fn par_keys(&self) -> ParKeys<'_, K, V>
+    where
+        K: Sync,
+        V: Sync,
+    {
+        ParKeys {
+            inner: self.entries_slice().into_par_iter(),
+        }
+    }
+
+    /// Returns a parallel iterator over the values of the map.
+    ///
+    /// This function requires the feature `rayon`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    /// use rayon::prelude::*;
+    ///
+    /// let map: OmniMap<u32, u32> = (0..100).map(|i| (i, i)).collect();
+    ///
+    /// assert_eq!(map.par_values().max(), Some(&99));
+    /// ```
+    #[inline]
+    pub fn par_values(&self) -> ParValues<'_, K, V>
+    where
+        K: Sync,
+        V: Sync,
+    {
+        ParValues {
+            inner: self.entries_slice().into_par_iter(),
+        }
+    }
+}
+
+impl<K, V, S, A> IntoParallelIterator for OmniMap<K, V, S, A>
+where
+    K: Send,
+    V: Send,
+    A: Allocator,
+{
+    type Iter = IntoParIter<K, V>;
+    type Item = (K, V);
+
+    /// Consumes the map and returns a parallel iterator over its entries.
+    ///
+    /// The entries are moved out of the map in order before they are split.
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        IntoParIter {
+            inner: self.into_iter().collect::<Vec<_>>().into_par_iter(),
+        }
+    }
+}
+
+impl<'a, K, V, S, A> IntoParallelIterator for &'a OmniMap<K, V, S, A>
+where
+    K: Sync,
+    V: Sync,
+    A: Allocator,
+{
+    type Iter = ParIter<'a, K, V>;
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter()
+    }
+}
+
+impl<'a, K, V, S, A> IntoParallelIterator for &'a mut OmniMap<K, V, S, A>
+where
+    K: Send + Sync,
+    V: Send,
+    A: Allocator,
+{
+    type Iter = ParIterMut<'a, K, V>;
+    type Item = (&'a K, &'a mut V);
+
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter_mut()
+    }
+}
+
+impl<K, V, S, A> FromParallelIterator<(K, V)> for OmniMap<K, V, S, A>
+where
+    K: Eq + Hash + Send,
+    V: Send,
+    S: BuildHasher + Default + Send,
+    A: Allocator + Default + Send,
+{
+    /// Creates a new map from the entries of a parallel iterator.
+    ///
+    /// The entries are inserted in the order of the iterator, and the policy of the duplicate
+    /// keys is the same as [`OmniMap::insert`].
+    #[inline]
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        let mut map = Self::with_hasher_in(S::default(), A::default());
+        map.par_extend(par_iter);
+        map
+    }
+}
+
+impl<K, V, S, A> ParallelExtend<(K, V)> for OmniMap<K, V, S, A>
+where
+    K: Eq + Hash + Send,
+    V: Send,
+    S: BuildHasher + Send,
+    A: Allocator + Send,
+{
+    /// Extends the map with the entries of a parallel iterator.
+    ///
+    /// The entries are collected in parallel, then inserted in the order of the iterator.
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        let entries: Vec<(K, V)> = par_iter.into_par_iter().collect();
+        self.extend(entries);
+    }
+}
+
+impl<'a, K, V, S, A> ParallelExtend<(&'a K, &'a V)> for OmniMap<K, V, S, A>
+where
+    K: Eq + Hash + Copy + Send + Sync,
+    V: Copy + Send + Sync,
+    S: BuildHasher + Send,
+    A: Allocator + Send,
+{
+    #[inline]
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = (&'a K, &'a V)>,
+    {
+        self.par_extend(par_iter.into_par_iter().map(|(&key, &value)| (key, value)));
+    }
+}
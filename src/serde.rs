@@ -0,0 +1,329 @@
+use core::fmt;
+use core::hash::{BuildHasher, Hash};
+use core::marker::PhantomData;
+use core::mem;
+
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::alloc::Allocator;
+use crate::entry::Entry;
+use crate::map::OmniMap;
+use crate::set::OmniSet;
+use crate::slice::Slice;
+
+/// The policy of handling the duplicate keys of a map or the duplicate values of a set during
+/// deserialization.
+///
+/// The default policy is [`DuplicateKeys::LastWins`], which matches the behavior of inserting
+/// the items one by one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// Fails the deserialization on the first duplicate.
+    Error,
+    /// Keeps the first occurrence and discards the later ones.
+    FirstWins,
+    /// Keeps the position of the first occurrence, but updates it with the later ones.
+    ///
+    /// For maps, the value is replaced and the key is kept. For sets, the value is replaced.
+    #[default]
+    LastWins,
+}
+
+/// Upper bound of the bytes that can be reserved in advance from the size hint of the input.
+///
+/// The size hint is provided by the input and can't be trusted, so the map grows as usual
+/// beyond this limit.
+const MAX_PREALLOCATED_BYTES: usize = 1024 * 1024;
+
+/// Returns the capacity to reserve in advance for items of type `T` from the size hint.
+#[inline]
+fn cautious_capacity<T>(hint: Option<usize>) -> usize {
+    match mem::size_of::<T>() {
+        0 => 0,
+        size => hint.unwrap_or(0).min(MAX_PREALLOCATED_BYTES / size),
+    }
+}
+
+impl<K, V> Serialize for Slice<K, V>
+where
+    K: Serialize,
+    V: Serialize,
+{
+    /// Serializes the entries of the slice as a map in their current order.
+    fn serialize<T: Serializer>(&self, serializer: T) -> Result<T::Ok, T::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (key, value) in self.iter() {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<K, V, S, A> Serialize for OmniMap<K, V, S, A>
+where
+    K: Serialize,
+    V: Serialize,
+    A: Allocator,
+{
+    /// Serializes the entries of the map as a map in their current order.
+    #[inline]
+    fn serialize<T: Serializer>(&self, serializer: T) -> Result<T::Ok, T::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+impl<'de, K, V, S, A> Deserialize<'de> for OmniMap<K, V, S, A>
+where
+    K: Deserialize<'de> + Eq + Hash,
+    V: Deserialize<'de>,
+    S: BuildHasher + Default,
+    A: Allocator + Default,
+{
+    /// Deserializes the map from a map, with the policy [`DuplicateKeys::LastWins`].
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::deserialize_with_policy(deserializer, DuplicateKeys::LastWins)
+    }
+}
+
+impl<K, V, S, A> OmniMap<K, V, S, A>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+    A: Allocator + Default,
+{
+    /// Deserializes the map from a map, and handles the duplicate keys according to `policy`.
+    ///
+    /// The entries are inserted in the order of the input, and the map is reserved in advance
+    /// from the size hint of the input.
+    ///
+    /// This function requires the feature `serde`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(map)`: If the input is a valid map.
+    ///
+    /// - `Err(error)`: If the input is invalid, or if it contains a duplicate key and the policy
+    ///   is [`DuplicateKeys::Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::{DuplicateKeys, OmniMap};
+    ///
+    /// let json = r#"{"b": 1, "a": 2, "b": 3}"#;
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(json);
+    /// let map: OmniMap<String, u32> =
+    ///     OmniMap::deserialize_with_policy(&mut de, DuplicateKeys::FirstWins).unwrap();
+    ///
+    /// assert_eq!(map.get("b"), Some(&1));
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(json);
+    /// let result: Result<OmniMap<String, u32>, _> =
+    ///     OmniMap::deserialize_with_policy(&mut de, DuplicateKeys::Error);
+    ///
+    /// assert!(result.is_err());
+    /// ```
+    pub fn deserialize_with_policy<'de, D>(
+        deserializer: D,
+        policy: DuplicateKeys,
+    ) -> Result<Self, D::Error>
+    where
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(OmniMapVisitor {
+            policy,
+            marker: PhantomData,
+        })
+    }
+}
+
+struct OmniMapVisitor<K, V, S, A> {
+    policy: DuplicateKeys,
+    marker: PhantomData<(K, V, S, A)>,
+}
+
+impl<'de, K, V, S, A> Visitor<'de> for OmniMapVisitor<K, V, S, A>
+where
+    K: Deserialize<'de> + Eq + Hash,
+    V: Deserialize<'de>,
+    S: BuildHasher + Default,
+    A: Allocator + Default,
+{
+    type Value = OmniMap<K, V, S, A>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<M: MapAccess<'de>>(self, mut access: M) -> Result<Self::Value, M::Error> {
+        let mut map = OmniMap::with_capacity_and_hasher_in(
+            cautious_capacity::<(K, V)>(access.size_hint()),
+            S::default(),
+            A::default(),
+        );
+
+        while let Some((key, value)) = access.next_entry()? {
+            match self.policy {
+                DuplicateKeys::LastWins => {
+                    map.insert(key, value);
+                }
+                DuplicateKeys::FirstWins => {
+                    if let Entry::Vacant(entry) = map.entry(key) {
+                        entry.insert(value);
+                    }
+                }
+                DuplicateKeys::Error => match map.entry(key) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(value);
+                    }
+                    Entry::Occupied(entry) => {
+                        return Err(M::Error::custom(format_args!(
+                            "duplicate key at index {}",
+                            entry.index()
+                        )));
+                    }
+                },
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+impl<T, S, A> Serialize for OmniSet<T, S, A>
+where
+    T: Serialize,
+    A: Allocator,
+{
+    /// Serializes the values of the set as a sequence in their current order.
+    #[inline]
+    fn serialize<U: Serializer>(&self, serializer: U) -> Result<U::Ok, U::Error> {
+        serializer.collect_seq(self.map.as_slice().keys())
+    }
+}
+
+impl<'de, T, S, A> Deserialize<'de> for OmniSet<T, S, A>
+where
+    T: Deserialize<'de> + Eq + Hash,
+    S: BuildHasher + Default,
+    A: Allocator + Default,
+{
+    /// Deserializes the set from a sequence, with the policy [`DuplicateKeys::LastWins`].
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::deserialize_with_policy(deserializer, DuplicateKeys::LastWins)
+    }
+}
+
+impl<T, S, A> OmniSet<T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    A: Allocator + Default,
+{
+    /// Deserializes the set from a sequence, and handles the duplicate values according to
+    /// `policy`.
+    ///
+    /// The values are inserted in the order of the input, and the set is reserved in advance
+    /// from the size hint of the input.
+    ///
+    /// This function requires the feature `serde`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(set)`: If the input is a valid sequence.
+    ///
+    /// - `Err(error)`: If the input is invalid, or if it contains a duplicate value and the
+    ///   policy is [`DuplicateKeys::Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::{DuplicateKeys, OmniSet};
+    ///
+    /// let json = "[3, 1, 3, 2]";
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(json);
+    /// let set: OmniSet<u32> =
+    ///     OmniSet::deserialize_with_policy(&mut de, DuplicateKeys::FirstWins).unwrap();
+    ///
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![&3, &1, &2]);
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(json);
+    /// let result: Result<OmniSet<u32>, _> =
+    ///     OmniSet::deserialize_with_policy(&mut de, DuplicateKeys::Error);
+    ///
+    /// assert!(result.is_err());
+    /// ```
+    pub fn deserialize_with_policy<'de, D>(
+        deserializer: D,
+        policy: DuplicateKeys,
+    ) -> Result<Self, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(OmniSetVisitor {
+            policy,
+            marker: PhantomData,
+        })
+    }
+}
+
+struct OmniSetVisitor<T, S, A> {
+    policy: DuplicateKeys,
+    marker: PhantomData<(T, S, A)>,
+}
+
+impl<'de, T, S, A> Visitor<'de> for OmniSetVisitor<T, S, A>
+where
+    T: Deserialize<'de> + Eq + Hash,
+    S: BuildHasher + Default,
+    A: Allocator + Default,
+{
+    type Value = OmniSet<T, S, A>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<Q: SeqAccess<'de>>(self, mut access: Q) -> Result<Self::Value, Q::Error> {
+        let mut set = OmniSet::with_capacity_and_hasher_in(
+            cautious_capacity::<T>(access.size_hint()),
+            S::default(),
+            A::default(),
+        );
+
+        while let Some(value) = access.next_element()? {
+            match self.policy {
+                DuplicateKeys::FirstWins => {
+                    set.insert(value);
+                }
+                DuplicateKeys::LastWins => match set.get_index_of(&value) {
+                    // The values are equal, so the hash and the position in the index are the
+                    // same, and the value can be replaced in place.
+                    Some(index) => set.map.entries_slice_mut()[index].key = value,
+                    None => {
+                        set.insert(value);
+                    }
+                },
+                DuplicateKeys::Error => {
+                    let (index, inserted) = set.insert_full(value);
+                    if !inserted {
+                        return Err(Q::Error::custom(format_args!(
+                            "duplicate value at index {}",
+                            index
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(set)
+    }
+}
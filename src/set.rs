@@ -0,0 +1,1141 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::Debug;
+use core::hash::{BuildHasher, Hash};
+use core::iter::{Chain, FusedIterator};
+use core::ops::Index;
+
+use crate::alloc::{Allocator, Global};
+use crate::entry::Entry;
+use crate::error::AllocError;
+use crate::hash::DefaultHashBuilder;
+use crate::iter::{IntoKeys, Keys};
+use crate::map::OmniMap;
+
+/// An insertion-ordered hash set, implemented as an [`OmniMap`] without values.
+///
+/// It has the same characteristics as the map: the order of the values is preserved during all
+/// operations, and the values can be accessed by their index. The set operations yield their
+/// results in the insertion order of the receiver.
+///
+/// # Examples
+///
+/// ```
+/// use omnimap::OmniSet;
+///
+/// let mut set = OmniSet::new();
+///
+/// set.insert("b");
+/// set.insert("a");
+/// set.insert("b");
+///
+/// assert_eq!(set.len(), 2);
+/// assert_eq!(set.iter().collect::<Vec<_>>(), vec![&"b", &"a"]);
+/// ```
+pub struct OmniSet<T, S = DefaultHashBuilder, A: Allocator = Global> {
+    pub(crate) map: OmniMap<T, (), S, A>,
+}
+
+// Constructors with the default hasher.
+impl<T> OmniSet<T> {
+    /// Returns a new `OmniSet` without allocated capacity.
+    ///
+    /// This function requires the feature `std`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            map: OmniMap::new(),
+        }
+    }
+
+    /// Returns a new `OmniSet` without allocated capacity, which uses the default hasher with the
+    /// specified `seed`.
+    ///
+    /// The iteration order doesn't depend on the seed, but the layout of the index does.
+    #[must_use]
+    #[inline]
+    pub const fn with_seed(seed: u64) -> Self {
+        Self {
+            map: OmniMap::with_seed(seed),
+        }
+    }
+
+    /// Creates a new `OmniSet` with the specified `capacity`.
+    ///
+    /// This function requires the feature `std`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if capacity overflow occurs, or when allocation fails.
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            map: OmniMap::with_capacity(capacity),
+        }
+    }
+}
+
+// Constructors with a custom hasher.
+impl<T, S> OmniSet<T, S> {
+    /// Returns a new `OmniSet` without allocated capacity, which uses `hash_builder` to hash the
+    /// values.
+    #[must_use]
+    #[inline]
+    pub const fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            map: OmniMap::with_hasher(hash_builder),
+        }
+    }
+
+    /// Creates a new `OmniSet` with the specified `capacity`, which uses `hash_builder` to hash
+    /// the values.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if capacity overflow occurs, or when allocation fails.
+    #[must_use]
+    #[inline]
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        Self {
+            map: OmniMap::with_capacity_and_hasher(capacity, hash_builder),
+        }
+    }
+}
+
+// Constructors with the default hasher and a custom allocator.
+impl<T, A: Allocator> OmniSet<T, DefaultHashBuilder, A> {
+    /// Returns a new `OmniSet` without allocated capacity, which allocates its memory with
+    /// `alloc`.
+    ///
+    /// This function requires the feature `std`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub fn new_in(alloc: A) -> Self {
+        Self {
+            map: OmniMap::new_in(alloc),
+        }
+    }
+
+    /// Creates a new `OmniSet` with the specified `capacity`, which allocates its memory with
+    /// `alloc`.
+    ///
+    /// This function requires the feature `std`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if capacity overflow occurs, or when allocation fails.
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self {
+            map: OmniMap::with_capacity_in(capacity, alloc),
+        }
+    }
+}
+
+impl<T, S, A: Allocator> OmniSet<T, S, A> {
+    /// Returns a new `OmniSet` without allocated capacity, which uses `hash_builder` to hash the
+    /// values and allocates its memory with `alloc`.
+    #[must_use]
+    #[inline]
+    pub const fn with_hasher_in(hash_builder: S, alloc: A) -> Self {
+        Self {
+            map: OmniMap::with_hasher_in(hash_builder, alloc),
+        }
+    }
+
+    /// Creates a new `OmniSet` with the specified `capacity`, which uses `hash_builder` to hash
+    /// the values and allocates its memory with `alloc`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if capacity overflow occurs, or when allocation fails.
+    #[must_use]
+    #[inline]
+    pub fn with_capacity_and_hasher_in(capacity: usize, hash_builder: S, alloc: A) -> Self {
+        Self {
+            map: OmniMap::with_capacity_and_hasher_in(capacity, hash_builder, alloc),
+        }
+    }
+
+    /// Returns a reference to the hash builder of the set.
+    #[must_use]
+    #[inline(always)]
+    pub const fn hasher(&self) -> &S {
+        self.map.hasher()
+    }
+
+    /// Returns a reference to the allocator of the set.
+    #[must_use]
+    #[inline(always)]
+    pub const fn allocator(&self) -> &A {
+        self.map.allocator()
+    }
+}
+
+// Core implementation
+impl<T, S, A> OmniSet<T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Returns the number of values the set can hold without reallocating.
+    #[must_use]
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Returns the number of values in the set.
+    #[must_use]
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set contains no values.
+    #[must_use]
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns an iterator over the values in the set in their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniSet;
+    ///
+    /// let set = OmniSet::from([3, 1, 2]);
+    ///
+    /// assert_eq!(set.iter().rev().collect::<Vec<_>>(), vec![&2, &1, &3]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> SetIter<'_, T> {
+        SetIter {
+            inner: self.map.iter_keys(),
+        }
+    }
+
+    /// Reserves capacity for `additional` values in advance.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if capacity overflow occurs, or when allocation fails.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.map.reserve(additional);
+    }
+
+    /// Tries to reserve capacity for `additional` values in advance.
+    ///
+    /// This method is semantically equivalent to [`OmniSet::reserve`], except that it returns an
+    /// error instead of panicking when the allocation fails.
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        self.map.try_reserve(additional)
+    }
+
+    /// Shrinks the capacity of the set as much as possible.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic when allocation fails.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.map.shrink_to_fit();
+    }
+
+    /// Adds `value` to the end of the set, if it is not already present.
+    ///
+    /// # Returns
+    ///
+    /// - `true`: If the value has been inserted.
+    ///
+    /// - `false`: If the value is already present. The set is not modified in this case.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if capacity overflow occurs, or when allocation fails.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniSet;
+    ///
+    /// let mut set = OmniSet::new();
+    ///
+    /// assert!(set.insert(1));
+    /// assert!(!set.insert(1));
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, value: T) -> bool {
+        self.insert_full(value).1
+    }
+
+    /// Adds `value` to the end of the set, if it is not already present.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the index of the value, and `true` if it has been inserted or `false` if it is
+    /// already present.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if capacity overflow occurs, or when allocation fails.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniSet;
+    ///
+    /// let mut set = OmniSet::new();
+    ///
+    /// assert_eq!(set.insert_full("a"), (0, true));
+    /// assert_eq!(set.insert_full("b"), (1, true));
+    /// assert_eq!(set.insert_full("a"), (0, false));
+    /// ```
+    pub fn insert_full(&mut self, value: T) -> (usize, bool) {
+        // Present values are not replaced, unlike the keys of the map.
+        match self.map.entry(value) {
+            Entry::Occupied(entry) => (entry.index(), false),
+            Entry::Vacant(entry) => {
+                let index = entry.index();
+                entry.insert(());
+                (index, true)
+            }
+        }
+    }
+
+    /// Inserts `value` at the specified `index`, if it is not already present, and shifts the
+    /// values after it.
+    ///
+    /// If the value is already present, it is moved to `index`.
+    ///
+    /// # Returns
+    ///
+    /// - `true`: If the value has been inserted.
+    ///
+    /// - `false`: If the value is already present.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is out of bounds, or when allocation fails.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n) where `n` is the number of the shifted values.
+    #[inline]
+    pub fn shift_insert(&mut self, index: usize, value: T) -> bool {
+        self.map.shift_insert(index, value, ()).is_none()
+    }
+
+    /// Returns `true` if the set contains `value`.
+    ///
+    /// The `value` may be any borrowed form of the value type, but `Hash` and `Eq` on the
+    /// borrowed form must match those of the value type.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    #[must_use]
+    #[inline]
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// Returns a reference to the value in the set that is equal to `value`.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    #[must_use]
+    #[inline]
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.map.get_key_value(value).map(|(key, _)| key)
+    }
+
+    /// Returns the index of `value` in the set.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    #[must_use]
+    #[inline]
+    pub fn get_index_of<Q>(&self, value: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.map.get_index_of(value)
+    }
+
+    /// Returns the value at the specified `index`, or `None` if the index is out of bounds.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    #[must_use]
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<&T> {
+        self.map.get_index(index).map(|(key, _)| key)
+    }
+
+    /// Returns the first value in the set, or `None` if the set is empty.
+    #[must_use]
+    #[inline]
+    pub fn first(&self) -> Option<&T> {
+        self.map.first().map(|(key, _)| key)
+    }
+
+    /// Returns the last value in the set, or `None` if the set is empty.
+    #[must_use]
+    #[inline]
+    pub fn last(&self) -> Option<&T> {
+        self.map.last().map(|(key, _)| key)
+    }
+
+    /// Removes `value` from the set and shifts the values after it, preserving the order.
+    ///
+    /// # Returns
+    ///
+    /// - `true`: If the value has been removed.
+    ///
+    /// - `false`: If the value is not present.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n) where `n` is the number of the shifted values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniSet;
+    ///
+    /// let mut set = OmniSet::from([1, 2, 3]);
+    ///
+    /// assert!(set.shift_remove(&1));
+    /// assert!(!set.shift_remove(&1));
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![&2, &3]);
+    /// ```
+    #[inline]
+    pub fn shift_remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.map.shift_remove(value).is_some()
+    }
+
+    /// Removes `value` from the set and moves the last value to its place.
+    ///
+    /// # Returns
+    ///
+    /// - `true`: If the value has been removed.
+    ///
+    /// - `false`: If the value is not present.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniSet;
+    ///
+    /// let mut set = OmniSet::from([1, 2, 3]);
+    ///
+    /// assert!(set.swap_remove(&1));
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![&3, &2]);
+    /// ```
+    #[inline]
+    pub fn swap_remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.map.swap_remove(value).is_some()
+    }
+
+    /// Removes and returns the first value in the set, or `None` if the set is empty.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(n).
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.map.pop_front().map(|(key, _)| key)
+    }
+
+    /// Removes and returns the last value in the set, or `None` if the set is empty.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1) on average.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.map.pop().map(|(key, _)| key)
+    }
+
+    /// Moves the value at the index `from` to the index `to`, and shifts the values in between.
+    ///
+    /// # Panics
+    ///
+    /// If `from` or `to` is out of bounds.
+    #[inline]
+    pub fn move_index(&mut self, from: usize, to: usize) {
+        self.map.move_index(from, to);
+    }
+
+    /// Swaps the positions of the values at the indices `a` and `b`.
+    ///
+    /// # Panics
+    ///
+    /// If `a` or `b` is out of bounds.
+    #[inline]
+    pub fn swap_indices(&mut self, a: usize, b: usize) {
+        self.map.swap_indices(a, b);
+    }
+
+    /// Reverses the order of the values in the set.
+    #[inline]
+    pub fn reverse(&mut self) {
+        self.map.reverse();
+    }
+
+    /// Sorts the values in the set.
+    ///
+    /// The sort is stable.
+    #[inline]
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.map.sort_keys();
+    }
+
+    /// Sorts the values in the set with the comparator function `compare`.
+    ///
+    /// The sort is stable.
+    #[inline]
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.map.sort_by(|a, _, b, _| compare(a, b));
+    }
+
+    /// Retains only the values for which the predicate `keep` returns `true`.
+    #[inline]
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.map.retain(|key, _| keep(key));
+    }
+
+    /// Shortens the set, keeping the first `len` values and dropping the rest.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.map.truncate(len);
+    }
+
+    /// Clears the set, removing all values.
+    ///
+    /// The allocated capacity is not changed.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns an iterator over the values that are in `self` or in `other`, without duplicates.
+    ///
+    /// The values of `self` are yielded first in their order, followed by the values of `other`
+    /// that are not in `self` in their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniSet;
+    ///
+    /// let a = OmniSet::from([1, 2, 3]);
+    /// let b = OmniSet::from([4, 2, 5]);
+    ///
+    /// assert_eq!(a.union(&b).collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5]);
+    /// ```
+    #[inline]
+    pub fn union<'a, S2>(&'a self, other: &'a OmniSet<T, S2, A>) -> Union<'a, T, S, A>
+    where
+        S2: BuildHasher,
+    {
+        Union {
+            inner: self.iter().chain(other.difference(self)),
+        }
+    }
+
+    /// Returns an iterator over the values that are in both `self` and `other`, in the order of
+    /// `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniSet;
+    ///
+    /// let a = OmniSet::from([1, 2, 3, 4]);
+    /// let b = OmniSet::from([4, 2, 5]);
+    ///
+    /// assert_eq!(a.intersection(&b).collect::<Vec<_>>(), vec![&2, &4]);
+    /// ```
+    #[inline]
+    pub fn intersection<'a, S2>(
+        &'a self,
+        other: &'a OmniSet<T, S2, A>,
+    ) -> Intersection<'a, T, S2, A>
+    where
+        S2: BuildHasher,
+    {
+        Intersection {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Returns an iterator over the values that are in `self` but not in `other`, in the order
+    /// of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniSet;
+    ///
+    /// let a = OmniSet::from([1, 2, 3, 4]);
+    /// let b = OmniSet::from([4, 2, 5]);
+    ///
+    /// assert_eq!(a.difference(&b).collect::<Vec<_>>(), vec![&1, &3]);
+    /// ```
+    #[inline]
+    pub fn difference<'a, S2>(&'a self, other: &'a OmniSet<T, S2, A>) -> Difference<'a, T, S2, A>
+    where
+        S2: BuildHasher,
+    {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Returns an iterator over the values that are in `self` or in `other`, but not in both.
+    ///
+    /// The values of `self` are yielded first in their order, followed by the values of `other`
+    /// in their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniSet;
+    ///
+    /// let a = OmniSet::from([1, 2, 3]);
+    /// let b = OmniSet::from([4, 2, 5]);
+    ///
+    /// assert_eq!(a.symmetric_difference(&b).collect::<Vec<_>>(), vec![&1, &3, &4, &5]);
+    /// ```
+    #[inline]
+    pub fn symmetric_difference<'a, S2>(
+        &'a self,
+        other: &'a OmniSet<T, S2, A>,
+    ) -> SymmetricDifference<'a, T, S, S2, A>
+    where
+        S2: BuildHasher,
+    {
+        SymmetricDifference {
+            inner: self.difference(other).chain(other.difference(self)),
+        }
+    }
+
+    /// Returns `true` if all the values of `self` are in `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniSet;
+    ///
+    /// let a = OmniSet::from([1, 2]);
+    /// let b = OmniSet::from([3, 2, 1]);
+    ///
+    /// assert!(a.is_subset(&b));
+    /// assert!(!b.is_subset(&a));
+    /// ```
+    #[must_use]
+    pub fn is_subset<S2>(&self, other: &OmniSet<T, S2, A>) -> bool
+    where
+        S2: BuildHasher,
+    {
+        self.len() <= other.len() && self.iter().all(|value| other.contains(value))
+    }
+
+    /// Returns `true` if all the values of `other` are in `self`.
+    #[must_use]
+    #[inline]
+    pub fn is_superset<S2>(&self, other: &OmniSet<T, S2, A>) -> bool
+    where
+        S2: BuildHasher,
+    {
+        other.is_subset(self)
+    }
+
+    /// Returns `true` if `self` and `other` have no values in common.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniSet;
+    ///
+    /// let a = OmniSet::from([1, 2]);
+    ///
+    /// assert!(a.is_disjoint(&OmniSet::from([3, 4])));
+    /// assert!(!a.is_disjoint(&OmniSet::from([2, 3])));
+    /// ```
+    #[must_use]
+    pub fn is_disjoint<S2>(&self, other: &OmniSet<T, S2, A>) -> bool
+    where
+        S2: BuildHasher,
+    {
+        // Probing the larger set with the values of the smaller one.
+        if self.len() <= other.len() {
+            self.iter().all(|value| !other.contains(value))
+        } else {
+            other.iter().all(|value| !self.contains(value))
+        }
+    }
+}
+
+impl<T, S, A> Clone for OmniSet<T, S, A>
+where
+    T: Eq + Hash + Clone,
+    S: BuildHasher + Clone,
+    A: Allocator + Clone,
+{
+    /// Clones the set with the same capacity.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic when allocation fails.
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<T, S, A> Default for OmniSet<T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    A: Allocator + Default,
+{
+    /// Creates a new `OmniSet` without allocated capacity, with the default hash builder and the
+    /// default allocator.
+    #[inline]
+    fn default() -> Self {
+        Self::with_hasher_in(S::default(), A::default())
+    }
+}
+
+impl<T, S, A> PartialEq for OmniSet<T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Returns `true` if both sets contain the same values, regardless of their order.
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.is_subset(other)
+    }
+}
+
+impl<T, S, A> Eq for OmniSet<T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+}
+
+impl<T, S, A> Debug for OmniSet<T, S, A>
+where
+    T: Eq + Hash + Debug,
+    S: BuildHasher,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T, S, A: Allocator> Index<usize> for OmniSet<T, S, A> {
+    type Output = T;
+
+    /// Returns a reference to the value at the specified `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        match self.map.as_slice().get_index(index) {
+            Some((key, _)) => key,
+            None => panic!("Index out of bounds."),
+        }
+    }
+}
+
+impl<T, S, A> FromIterator<T> for OmniSet<T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    A: Allocator + Default,
+{
+    /// Creates a new `OmniSet` from the values of `iter`, keeping the first occurrence of each
+    /// value.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::with_hasher_in(S::default(), A::default());
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S, A> Extend<T> for OmniSet<T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Adds the values of `iter` that are not already present to the end of the set.
+    ///
+    /// The capacity is reserved in advance according to the lower bound of the size hint of
+    /// `iter`.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+
+        // Same policy as the map, only half of the hint is reserved if some values might be
+        // already present.
+        let hint = iter.size_hint().0;
+        let additional = if self.is_empty() {
+            hint
+        } else {
+            hint.div_ceil(2)
+        };
+        self.map.reserve_free(additional);
+
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a, T, S, A> Extend<&'a T> for OmniSet<T, S, A>
+where
+    T: Eq + Hash + Copy,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Adds copies of the values of `iter` that are not already present to the end of the set.
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, const N: usize> From<[T; N]> for OmniSet<T>
+where
+    T: Eq + Hash,
+{
+    /// Creates a new `OmniSet` from the values of `array`, keeping the first occurrence of each
+    /// value.
+    ///
+    /// This function requires the feature `std`.
+    #[inline]
+    fn from(array: [T; N]) -> Self {
+        Self::from_iter(array)
+    }
+}
+
+impl<'a, T, S, A> IntoIterator for &'a OmniSet<T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    type Item = &'a T;
+    type IntoIter = SetIter<'a, T>;
+
+    /// Returns an iterator over the values.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, S, A: Allocator> IntoIterator for OmniSet<T, S, A> {
+    type Item = T;
+    type IntoIter = OmniSetIterator<T, A>;
+
+    /// Consumes the `OmniSet` and returns an iterator over its values.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        OmniSetIterator {
+            inner: IntoKeys::new(self.map.into_iter()),
+        }
+    }
+}
+
+/// An iterator over the values of a set.
+///
+/// This struct is created by [`OmniSet::iter`].
+pub struct SetIter<'a, T> {
+    inner: Keys<'a, T, ()>,
+}
+
+/// An owning iterator over the values of a set.
+pub struct OmniSetIterator<T, A: Allocator = Global> {
+    inner: IntoKeys<T, (), A>,
+}
+
+// Both iterators forward to the iterators over the keys of the map.
+macro_rules! impl_set_iterator {
+    ([$($generics:tt)*] $ty:ty, $item:ty) => {
+        impl<$($generics)*> Iterator for $ty {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                self.inner.next()
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.inner.size_hint()
+            }
+
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                self.inner.nth(n)
+            }
+
+            #[inline]
+            fn last(self) -> Option<Self::Item> {
+                self.inner.last()
+            }
+
+            #[inline]
+            fn count(self) -> usize {
+                self.inner.len()
+            }
+        }
+
+        impl<$($generics)*> DoubleEndedIterator for $ty {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.inner.next_back()
+            }
+
+            #[inline]
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                self.inner.nth_back(n)
+            }
+        }
+
+        impl<$($generics)*> ExactSizeIterator for $ty {
+            /// Returns the number of remaining values in the iterator.
+            #[inline(always)]
+            fn len(&self) -> usize {
+                self.inner.len()
+            }
+        }
+
+        impl<$($generics)*> FusedIterator for $ty {}
+    };
+}
+
+impl_set_iterator!(['a, T] SetIter<'a, T>, &'a T);
+impl_set_iterator!([T, A: Allocator] OmniSetIterator<T, A>, T);
+
+impl<T> Clone for SetIter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// An iterator over the values of the union of two sets.
+///
+/// This struct is created by [`OmniSet::union`].
+pub struct Union<'a, T, S, A: Allocator = Global> {
+    inner: Chain<SetIter<'a, T>, Difference<'a, T, S, A>>,
+}
+
+/// An iterator over the values of the intersection of two sets.
+///
+/// This struct is created by [`OmniSet::intersection`].
+pub struct Intersection<'a, T, S, A: Allocator = Global> {
+    iter: SetIter<'a, T>,
+    other: &'a OmniSet<T, S, A>,
+}
+
+/// An iterator over the values of the difference of two sets.
+///
+/// This struct is created by [`OmniSet::difference`].
+pub struct Difference<'a, T, S, A: Allocator = Global> {
+    iter: SetIter<'a, T>,
+    other: &'a OmniSet<T, S, A>,
+}
+
+/// An iterator over the values of the symmetric difference of two sets.
+///
+/// This struct is created by [`OmniSet::symmetric_difference`].
+pub struct SymmetricDifference<'a, T, S, S2, A: Allocator = Global> {
+    inner: Chain<Difference<'a, T, S2, A>, Difference<'a, T, S, A>>,
+}
+
+impl<'a, T, S, A> Iterator for Intersection<'a, T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.find(|value| other.contains(*value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, T, S, A> Iterator for Difference<'a, T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.find(|value| !other.contains(*value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, T, S, A> Iterator for Union<'a, T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, S, S2, A> Iterator for SymmetricDifference<'a, T, S, S2, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    S2: BuildHasher,
+    A: Allocator,
+{
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T, S, A> FusedIterator for Intersection<'_, T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+}
+
+impl<T, S, A> FusedIterator for Difference<'_, T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+}
+
+impl<T, S, A> FusedIterator for Union<'_, T, S, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    A: Allocator,
+{
+}
+
+impl<T, S, S2, A> FusedIterator for SymmetricDifference<'_, T, S, S2, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    S2: BuildHasher,
+    A: Allocator,
+{
+}
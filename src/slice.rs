@@ -0,0 +1,609 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
+use core::ops::{
+    Index, IndexMut, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo,
+    RangeToInclusive,
+};
+
+use crate::alloc::Allocator;
+use crate::iter::{Iter, IterMut, Keys, Values, ValuesMut};
+use crate::map::{try_bounds_of, Bucket, OmniMap};
+
+/// A dynamically-sized view into a contiguous range of entries of a map.
+///
+/// The entries are in the same order as in the map. A mutable slice allows modifying the values,
+/// but not the keys or the order of the entries, because that would invalidate the index of the
+/// map.
+///
+/// This type is reached through [`OmniMap::as_slice`], [`OmniMap::get_range`] or by indexing
+/// the map with a range.
+#[repr(transparent)]
+pub struct Slice<K, V> {
+    entries: [Bucket<K, V>],
+}
+
+impl<K, V> Slice<K, V> {
+    /// Returns a slice view of the `entries`.
+    #[inline(always)]
+    pub(crate) const fn from_entries(entries: &[Bucket<K, V>]) -> &Self {
+        // Safety: `Slice` is a transparent wrapper of `[Bucket<K, V>]`.
+        unsafe { &*(entries as *const [Bucket<K, V>] as *const Self) }
+    }
+
+    /// Returns a mutable slice view of the `entries`.
+    #[inline(always)]
+    pub(crate) const fn from_entries_mut(entries: &mut [Bucket<K, V>]) -> &mut Self {
+        // Safety: `Slice` is a transparent wrapper of `[Bucket<K, V>]`.
+        unsafe { &mut *(entries as *mut [Bucket<K, V>] as *mut Self) }
+    }
+
+    /// Returns the number of entries in the slice.
+    #[must_use]
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the slice contains no entries.
+    #[must_use]
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the entry at the specified `index` of the slice.
+    ///
+    /// # Returns
+    ///
+    /// - `Some((&key, &value))`: If the index is within bounds.
+    ///
+    /// - `None`: If the index is out of bounds.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 3);
+    ///
+    /// let slice = &map[1..];
+    ///
+    /// assert_eq!(slice.get_index(0), Some((&"b", &2)));
+    /// assert_eq!(slice.get_index(2), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        let entry = self.entries.get(index)?;
+        Some((&entry.key, &entry.value))
+    }
+
+    /// Returns the entry at the specified `index` of the slice with a mutable reference to the
+    /// value.
+    ///
+    /// # Returns
+    ///
+    /// - `Some((&key, &mut value))`: If the index is within bounds.
+    ///
+    /// - `None`: If the index is out of bounds.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    #[must_use]
+    #[inline]
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        let entry = self.entries.get_mut(index)?;
+        Some((&entry.key, &mut entry.value))
+    }
+
+    /// Returns a sub-slice of the entries in the specified `range` of the slice.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(slice)`: If the range is within bounds.
+    ///
+    /// - `None`: If the start of the range is greater than its end, or if the end of the range
+    ///   is greater than the length of the slice.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// for i in 0..5 {
+    ///     map.insert(i, i * 10);
+    /// }
+    ///
+    /// let slice = map.as_slice().get_range(1..4).unwrap();
+    /// let sub = slice.get_range(1..).unwrap();
+    ///
+    /// assert_eq!(sub.keys().collect::<Vec<_>>(), vec![&2, &3]);
+    /// assert!(slice.get_range(2..4).is_none());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn get_range<R: RangeBounds<usize>>(&self, range: R) -> Option<&Self> {
+        let range = try_bounds_of(range, self.entries.len())?;
+        Some(Self::from_entries(&self.entries[range]))
+    }
+
+    /// Returns a mutable sub-slice of the entries in the specified `range` of the slice.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(slice)`: If the range is within bounds.
+    ///
+    /// - `None`: If the start of the range is greater than its end, or if the end of the range
+    ///   is greater than the length of the slice.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    #[must_use]
+    #[inline]
+    pub fn get_range_mut<R: RangeBounds<usize>>(&mut self, range: R) -> Option<&mut Self> {
+        let range = try_bounds_of(range, self.entries.len())?;
+        Some(Self::from_entries_mut(&mut self.entries[range]))
+    }
+
+    /// Returns the first entry of the slice, or `None` if the slice is empty.
+    #[must_use]
+    #[inline]
+    pub const fn first(&self) -> Option<(&K, &V)> {
+        match self.entries.first() {
+            Some(entry) => Some((&entry.key, &entry.value)),
+            None => None,
+        }
+    }
+
+    /// Returns the first entry of the slice with a mutable reference to the value, or `None` if
+    /// the slice is empty.
+    #[must_use]
+    #[inline]
+    pub const fn first_mut(&mut self) -> Option<(&K, &mut V)> {
+        match self.entries.first_mut() {
+            Some(entry) => Some((&entry.key, &mut entry.value)),
+            None => None,
+        }
+    }
+
+    /// Returns the last entry of the slice, or `None` if the slice is empty.
+    #[must_use]
+    #[inline]
+    pub const fn last(&self) -> Option<(&K, &V)> {
+        match self.entries.last() {
+            Some(entry) => Some((&entry.key, &entry.value)),
+            None => None,
+        }
+    }
+
+    /// Returns the last entry of the slice with a mutable reference to the value, or `None` if
+    /// the slice is empty.
+    #[must_use]
+    #[inline]
+    pub const fn last_mut(&mut self) -> Option<(&K, &mut V)> {
+        match self.entries.last_mut() {
+            Some(entry) => Some((&entry.key, &mut entry.value)),
+            None => None,
+        }
+    }
+
+    /// Divides the slice into two at the specified `index`.
+    ///
+    /// The first slice contains the entries in the range `[0, index)`, and the second contains
+    /// the entries in the range `[index, len)`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is greater than the length of the slice.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// for i in 0..5 {
+    ///     map.insert(i, i * 10);
+    /// }
+    ///
+    /// let (head, tail) = map.as_slice().split_at(2);
+    ///
+    /// assert_eq!(head.keys().collect::<Vec<_>>(), vec![&0, &1]);
+    /// assert_eq!(tail.keys().collect::<Vec<_>>(), vec![&2, &3, &4]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn split_at(&self, index: usize) -> (&Self, &Self) {
+        assert!(index <= self.entries.len(), "Index out of bounds.");
+        let (head, tail) = self.entries.split_at(index);
+        (Self::from_entries(head), Self::from_entries(tail))
+    }
+
+    /// Divides the mutable slice into two at the specified `index`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `index` is greater than the length of the slice.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(1).
+    #[must_use]
+    #[inline]
+    pub fn split_at_mut(&mut self, index: usize) -> (&mut Self, &mut Self) {
+        assert!(index <= self.entries.len(), "Index out of bounds.");
+        let (head, tail) = self.entries.split_at_mut(index);
+        (Self::from_entries_mut(head), Self::from_entries_mut(tail))
+    }
+
+    /// Returns the first entry and the rest of the slice, or `None` if the slice is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    ///
+    /// let (first, rest) = map.as_slice().split_first().unwrap();
+    ///
+    /// assert_eq!(first, (&"a", &1));
+    /// assert_eq!(rest.len(), 1);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn split_first(&self) -> Option<((&K, &V), &Self)> {
+        let (first, rest) = self.entries.split_first()?;
+        Some(((&first.key, &first.value), Self::from_entries(rest)))
+    }
+
+    /// Returns the first entry with a mutable reference to the value and the rest of the slice,
+    /// or `None` if the slice is empty.
+    #[must_use]
+    #[inline]
+    pub fn split_first_mut(&mut self) -> Option<((&K, &mut V), &mut Self)> {
+        let (first, rest) = self.entries.split_first_mut()?;
+        Some(((&first.key, &mut first.value), Self::from_entries_mut(rest)))
+    }
+
+    /// Returns the last entry and the rest of the slice, or `None` if the slice is empty.
+    #[must_use]
+    #[inline]
+    pub fn split_last(&self) -> Option<((&K, &V), &Self)> {
+        let (last, rest) = self.entries.split_last()?;
+        Some(((&last.key, &last.value), Self::from_entries(rest)))
+    }
+
+    /// Returns the last entry with a mutable reference to the value and the rest of the slice,
+    /// or `None` if the slice is empty.
+    #[must_use]
+    #[inline]
+    pub fn split_last_mut(&mut self) -> Option<((&K, &mut V), &mut Self)> {
+        let (last, rest) = self.entries.split_last_mut()?;
+        Some(((&last.key, &mut last.value), Self::from_entries_mut(rest)))
+    }
+
+    /// Searches the slice for the key `key` with binary search.
+    ///
+    /// The slice must be sorted by key, otherwise the result is unspecified but memory-safe.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(index)`: If the key is found. If there are multiple matches, any of them can be
+    ///   returned.
+    ///
+    /// - `Err(index)`: If the key is not found, where `index` is the position at which the key
+    ///   can be inserted while maintaining the sorted order.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(log n).
+    #[inline]
+    pub fn binary_search_keys(&self, key: &K) -> Result<usize, usize>
+    where
+        K: Ord,
+    {
+        self.binary_search_by(|k, _| k.cmp(key))
+    }
+
+    /// Searches the slice with binary search, using the comparator function `f`.
+    ///
+    /// The comparator must return the ordering of the entry relative to the target, and the
+    /// slice must be sorted consistently with it, otherwise the result is unspecified but
+    /// memory-safe.
+    ///
+    /// # Returns
+    ///
+    /// The same as [`Slice::binary_search_keys`].
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(log n).
+    #[inline]
+    pub fn binary_search_by<'a, F>(&'a self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&'a K, &'a V) -> Ordering,
+    {
+        self.entries
+            .binary_search_by(|entry| f(&entry.key, &entry.value))
+    }
+
+    /// Searches the slice with binary search for the key `b` extracted by the function `f`.
+    ///
+    /// The slice must be sorted by the extracted key, otherwise the result is unspecified but
+    /// memory-safe.
+    ///
+    /// # Returns
+    ///
+    /// The same as [`Slice::binary_search_keys`].
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(log n).
+    #[inline]
+    pub fn binary_search_by_key<'a, B, F>(&'a self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&'a K, &'a V) -> B,
+        B: Ord,
+    {
+        self.binary_search_by(|k, v| f(k, v).cmp(b))
+    }
+
+    /// Returns the index of the partition point of the slice according to the predicate `pred`.
+    ///
+    /// The slice must be partitioned, i.e. all the entries for which the predicate returns `true`
+    /// must precede all the entries for which it returns `false`, otherwise the result is
+    /// unspecified but memory-safe.
+    ///
+    /// # Time Complexity
+    ///
+    /// _O_(log n).
+    #[must_use]
+    #[inline]
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&K, &V) -> bool,
+    {
+        self.entries
+            .partition_point(|entry| pred(&entry.key, &entry.value))
+    }
+
+    /// Returns an iterator over the entries of the slice.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(&self.entries)
+    }
+
+    /// Returns an iterator over the entries of the slice with mutable references to the values.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(&mut self.entries)
+    }
+
+    /// Returns an iterator over the keys of the slice.
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(&self.entries)
+    }
+
+    /// Returns an iterator over the values of the slice.
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values::new(&self.entries)
+    }
+
+    /// Returns an iterator over mutable references to the values of the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use omnimap::OmniMap;
+    ///
+    /// let mut map = OmniMap::new();
+    ///
+    /// for i in 0..4 {
+    ///     map.insert(i, i);
+    /// }
+    ///
+    /// for value in map[2..].values_mut() {
+    ///     *value *= 10;
+    /// }
+    ///
+    /// assert_eq!(map.iter_values().collect::<Vec<_>>(), vec![&0, &1, &20, &30]);
+    /// ```
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(&mut self.entries)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a Slice<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut Slice<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V> Index<usize> for Slice<K, V> {
+    type Output = V;
+
+    /// Returns a reference to the value at the specified `index` of the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    fn index(&self, index: usize) -> &V {
+        assert!(index < self.entries.len(), "Index out of bounds.");
+        unsafe { &self.entries.get_unchecked(index).value }
+    }
+}
+
+impl<K, V> IndexMut<usize> for Slice<K, V> {
+    /// Returns a mutable reference to the value at the specified `index` of the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut V {
+        assert!(index < self.entries.len(), "Index out of bounds.");
+        unsafe { &mut self.entries.get_unchecked_mut(index).value }
+    }
+}
+
+// Range indexing of the slice and the map, which panics with the same message as `Index<usize>`.
+macro_rules! impl_index_range {
+    ($($range:ty),*) => {
+        $(
+            impl<K, V> Index<$range> for Slice<K, V> {
+                type Output = Slice<K, V>;
+
+                #[inline]
+                fn index(&self, range: $range) -> &Slice<K, V> {
+                    match self.get_range(range) {
+                        Some(slice) => slice,
+                        None => panic!("Index out of bounds."),
+                    }
+                }
+            }
+
+            impl<K, V> IndexMut<$range> for Slice<K, V> {
+                #[inline]
+                fn index_mut(&mut self, range: $range) -> &mut Slice<K, V> {
+                    match self.get_range_mut(range) {
+                        Some(slice) => slice,
+                        None => panic!("Index out of bounds."),
+                    }
+                }
+            }
+
+            impl<K, V, S, A: Allocator> Index<$range> for OmniMap<K, V, S, A> {
+                type Output = Slice<K, V>;
+
+                #[inline]
+                fn index(&self, range: $range) -> &Slice<K, V> {
+                    &self.as_slice()[range]
+                }
+            }
+
+            impl<K, V, S, A: Allocator> IndexMut<$range> for OmniMap<K, V, S, A> {
+                #[inline]
+                fn index_mut(&mut self, range: $range) -> &mut Slice<K, V> {
+                    &mut self.as_mut_slice()[range]
+                }
+            }
+        )*
+    };
+}
+
+impl_index_range!(
+    Range<usize>,
+    RangeFrom<usize>,
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeTo<usize>,
+    RangeToInclusive<usize>
+);
+
+impl<K, V> PartialEq for Slice<K, V>
+where
+    K: PartialEq,
+    V: PartialEq,
+{
+    /// Compares the entries of both slices in order.
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K, V> Eq for Slice<K, V>
+where
+    K: Eq,
+    V: Eq,
+{
+}
+
+impl<K, V> PartialOrd for Slice<K, V>
+where
+    K: PartialOrd,
+    V: PartialOrd,
+{
+    /// Compares the entries of both slices lexicographically.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<K, V> Ord for Slice<K, V>
+where
+    K: Ord,
+    V: Ord,
+{
+    /// Compares the entries of both slices lexicographically.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<K, V> Hash for Slice<K, V>
+where
+    K: Hash,
+    V: Hash,
+{
+    /// Hashes the entries of the slice in order.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for (key, value) in self.iter() {
+            key.hash(state);
+            value.hash(state);
+        }
+    }
+}
+
+impl<K, V> Debug for Slice<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -1,9 +1,16 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod map_tests {
+    use crate::alloc::{Allocator, Global};
+    use crate::entry::Entry;
     use crate::error::AllocError;
+    use crate::hash::RandomState;
     use crate::index::Tag;
     use crate::map::{OmniMap, OmniMapIterator};
-    use core::cell::RefCell;
+    use core::alloc::Layout;
+    use core::cell::{Cell, RefCell};
+    use core::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+    use core::mem;
+    use core::ptr::NonNull;
     use std::rc::Rc;
 
     #[test]
@@ -12,7 +19,7 @@ mod map_tests {
 
         assert!(map.is_empty());
         assert_eq!(map.len(), 0);
-        assert_eq!(map.debug_deleted(), 0);
+        assert_eq!(map.debug_occupied(), map.len());
         assert_eq!(map.capacity(), 0);
     }
 
@@ -21,7 +28,7 @@ mod map_tests {
         let map: OmniMap<u8, &str> = OmniMap::with_capacity(10);
 
         assert_eq!(map.len(), 0);
-        assert_eq!(map.debug_deleted(), 0);
+        assert_eq!(map.debug_occupied(), map.len());
         assert_eq!(map.capacity(), 10);
         assert_eq!(map.debug_allocated_cap(), 12);
     }
@@ -78,7 +85,7 @@ mod map_tests {
         let map: OmniMap<u8, &str> = OmniMap::default();
 
         assert_eq!(map.len(), 0);
-        assert_eq!(map.debug_deleted(), 0);
+        assert_eq!(map.debug_occupied(), map.len());
         assert_eq!(map.capacity(), 16);
     }
 
@@ -95,7 +102,7 @@ mod map_tests {
 
         // Map state.
         assert_eq!(map.len(), 3);
-        assert_eq!(map.debug_deleted(), 0);
+        assert_eq!(map.debug_occupied(), map.len());
         assert_eq!(map.capacity(), 3);
 
         // Check values.
@@ -240,7 +247,8 @@ mod map_tests {
 
     #[test]
     fn test_map_shift_remove() {
-        let mut map = OmniMap::new();
+        // The identity hasher places each key at the slot of its value.
+        let mut map = identity_map();
 
         assert_eq!(map.shift_remove(&1), None);
 
@@ -250,69 +258,132 @@ mod map_tests {
         assert_eq!(map.shift_remove(&1), Some(2));
 
         assert_eq!(map.len(), 0);
-        assert_eq!(map.debug_deleted(), 1);
         assert_eq!(map.capacity(), 3);
+        assert_eq!(slot_keys(&map), vec![None; 4]);
 
         // Must return None, because the map is empty.
         assert_eq!(map.shift_remove(&1), None);
 
-        // Insert new items.
-        for i in 1..5 {
+        // Insert new items, including `9` that collides with `1`.
+        for i in [1, 2, 9, 3, 4] {
             map.insert(i, i + 1);
         }
 
-        // Now, the map must have expanded its capacity and reset the deleted counter.
-        assert_eq!(map.len(), 4);
-        assert_eq!(map.debug_deleted(), 0);
+        // Now, the map must have expanded its capacity.
+        assert_eq!(map.len(), 5);
         assert_eq!(map.capacity(), 7);
+        assert_eq!(
+            slot_keys(&map),
+            vec![
+                None,
+                Some(1),
+                Some(2),
+                Some(9),
+                Some(3),
+                Some(4),
+                None,
+                None
+            ]
+        );
 
-        // Remove the second item (key "2").
+        // Remove the second item (key "2"). The displaced keys are shifted back.
         assert_eq!(map.shift_remove(&2), Some(3));
 
-        // Map state at this point.
-        assert_eq!(map.len(), 3);
-        assert_eq!(map.debug_deleted(), 1);
+        assert_eq!(map.len(), 4);
         assert_eq!(map.capacity(), 7);
+        assert_eq!(
+            slot_keys(&map),
+            vec![None, Some(1), Some(9), Some(3), Some(4), None, None, None]
+        );
 
-        let mut deleted = 0;
-        let mut occupied = 0;
-        let mut empty = 0;
-
-        for i in 0..map.debug_allocated_cap() {
-            match map.debug_tag(i) {
-                Tag::Deleted => {
-                    deleted += 1;
-                }
-                Tag::Occupied => {
-                    occupied += 1;
-                }
-                Tag::Empty => {
-                    empty += 1;
-                }
-            }
-        }
+        // Remove the head of the cluster. `9` can move back to its ideal slot.
+        assert_eq!(map.shift_remove(&1), Some(2));
 
-        // Expected index state at this point.
-        assert_eq!(deleted, 1);
-        assert_eq!(occupied, 3);
-        assert_eq!(empty, 4);
+        assert_eq!(map.len(), 3);
+        assert_eq!(
+            slot_keys(&map),
+            vec![None, Some(9), None, Some(3), Some(4), None, None, None]
+        );
 
         // Check the order of the remaining items.
         assert_eq!(
             map.iter().collect::<Vec<(&u8, &u8)>>(),
-            vec![(&1, &2), (&3, &4), (&4, &5)]
+            vec![(&9, &10), (&3, &4), (&4, &5)]
         );
 
         // Order of the keys must be preserved, but index has been updated.
-        assert_eq!(map[0], 2);
+        assert_eq!(map[0], 10);
         assert_eq!(map[1], 4);
         assert_eq!(map[2], 5);
     }
 
     #[test]
-    fn test_map_swap_remove() {
+    #[cfg_attr(miri, ignore)]
+    fn test_map_swap_remove_eq_panic() {
+        /// A key whose `Eq` panics once it is armed, and that is looked up by its borrowed `u32`
+        /// form.
+        struct PanicKey(u32, Rc<Cell<bool>>);
+
+        impl Hash for PanicKey {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+
+        impl PartialEq for PanicKey {
+            fn eq(&self, other: &Self) -> bool {
+                assert!(!self.1.get(), "Eq panic");
+                self.0 == other.0
+            }
+        }
+
+        impl Eq for PanicKey {}
+
+        impl core::borrow::Borrow<u32> for PanicKey {
+            fn borrow(&self) -> &u32 {
+                &self.0
+            }
+        }
+
+        let armed = Rc::new(Cell::new(false));
+        let count = Rc::new(RefCell::new(0));
         let mut map = OmniMap::new();
 
+        for i in 0..10 {
+            map.insert(
+                PanicKey(i, armed.clone()),
+                DropCounter {
+                    count: count.clone(),
+                },
+            );
+        }
+
+        armed.set(true);
+
+        // Moving the last entry to the removed position must not compare the keys.
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            map.swap_remove(&3);
+            map.swap_remove(&0);
+        }));
+
+        assert!(result.is_ok());
+        assert_eq!(*count.borrow(), 2);
+        assert_eq!(map.len(), 8);
+        assert_eq!(map.debug_occupied(), map.len());
+
+        for i in [1, 2, 4, 5, 6, 7, 8, 9] {
+            assert!(map.contains_key(&i));
+        }
+
+        drop(map);
+        assert_eq!(*count.borrow(), 10);
+    }
+
+    #[test]
+    fn test_map_swap_remove() {
+        // The identity hasher places each key at the slot of its value.
+        let mut map = identity_map();
+
         assert_eq!(map.swap_remove(&1), None);
 
         map.insert(1, "a");
@@ -321,8 +392,11 @@ mod map_tests {
         map.insert(4, "d");
 
         assert_eq!(map.len(), 4);
-        assert_eq!(map.debug_deleted(), 0);
         assert_eq!(map.capacity(), 7);
+        assert_eq!(
+            slot_keys(&map),
+            vec![None, Some(1), Some(2), Some(3), Some(4), None, None, None]
+        );
 
         assert_eq!(map[0], "a");
         assert_eq!(map[1], "b");
@@ -336,9 +410,13 @@ mod map_tests {
 
         assert_eq!(map.swap_remove(&1), Some("a"));
 
+        // The slot of `4` now references the first entry.
         assert_eq!(map.len(), 3);
-        assert_eq!(map.debug_deleted(), 1);
         assert_eq!(map.capacity(), 7);
+        assert_eq!(
+            slot_keys(&map),
+            vec![None, None, Some(2), Some(3), Some(4), None, None, None]
+        );
 
         assert_eq!(map[0], "d"); // <- last entry in its place
         assert_eq!(map[1], "b");
@@ -352,8 +430,11 @@ mod map_tests {
         assert_eq!(map.swap_remove(&4), Some("d"));
 
         assert_eq!(map.len(), 2);
-        assert_eq!(map.debug_deleted(), 2);
         assert_eq!(map.capacity(), 7);
+        assert_eq!(
+            slot_keys(&map),
+            vec![None, None, Some(2), Some(3), None, None, None, None]
+        );
 
         assert_eq!(map[0], "c"); // <- last entry in its place
         assert_eq!(map[1], "b");
@@ -366,8 +447,11 @@ mod map_tests {
         assert_eq!(map.swap_remove(&3), Some("c"));
 
         assert_eq!(map.len(), 1);
-        assert_eq!(map.debug_deleted(), 3);
         assert_eq!(map.capacity(), 7);
+        assert_eq!(
+            slot_keys(&map),
+            vec![None, None, Some(2), None, None, None, None, None]
+        );
 
         assert_eq!(map[0], "b");
 
@@ -379,8 +463,8 @@ mod map_tests {
         assert_eq!(map.swap_remove(&2), Some("b"));
 
         assert_eq!(map.len(), 0);
-        assert_eq!(map.debug_deleted(), 4);
         assert_eq!(map.capacity(), 7);
+        assert_eq!(slot_keys(&map), vec![None; 8]);
 
         assert_eq!(map.get(&1), None);
         assert_eq!(map.get(&2), None);
@@ -390,7 +474,8 @@ mod map_tests {
 
     #[test]
     fn test_map_pop_front() {
-        let mut map = OmniMap::new();
+        // The identity hasher places each key at the slot of its value.
+        let mut map = identity_map();
 
         // Pop when the map is empty must return None.
         assert_eq!(map.pop_front(), None);
@@ -404,66 +489,50 @@ mod map_tests {
         assert_eq!(key, 1);
         assert_eq!(value, 2);
         assert_eq!(map.len(), 0);
-        assert_eq!(map.debug_deleted(), 1);
-
-        assert!(map.debug_tag(0).is_deleted());
-
         assert_eq!(map.capacity(), 3);
 
+        // The slot of the removed entry must be freed.
+        assert_eq!(slot_keys(&map), vec![None; 4]);
+
         // Must return None, because the map is empty.
         assert_eq!(map.pop_front(), None);
-        assert_eq!(map.debug_deleted(), 1);
 
         // Insert new items.
         for i in 1..4 {
             map.insert(i, i + 1);
         }
 
-        // Now, the map must expand its capacity reset the deleted counter.
+        // The freed slot is reused without expanding the capacity.
         assert_eq!(map.len(), 3);
-        assert_eq!(map.debug_deleted(), 0);
-        assert_eq!(map.capacity(), 7);
+        assert_eq!(map.capacity(), 3);
+        assert_eq!(slot_keys(&map), vec![None, Some(1), Some(2), Some(3)]);
 
         // Pop the first item.
         assert_eq!(map.pop_front(), Some((1, 2)));
 
         // Map state at this point.
         assert_eq!(map.len(), 2);
-        assert_eq!(map.debug_deleted(), 1);
-        assert_eq!(map.capacity(), 7);
-
-        let mut deleted = 0;
-        let mut occupied = 0;
-        let mut empty = 0;
-
-        for i in 0..map.debug_allocated_cap() {
-            match map.debug_tag(i) {
-                Tag::Deleted => {
-                    deleted += 1;
-                }
-                Tag::Occupied => {
-                    occupied += 1;
-                }
-                Tag::Empty => {
-                    empty += 1;
-                }
-            }
-        }
-
-        // Expected index state at this point.
-        assert_eq!(deleted, 1);
-        assert_eq!(occupied, 2);
-        assert_eq!(empty, 5);
+        assert_eq!(map.capacity(), 3);
+        assert_eq!(slot_keys(&map), vec![None, None, Some(2), Some(3)]);
 
         // Expected values at this point.
         assert_eq!(map.get(&1), None);
         assert_eq!(map.get(&2), Some(&3));
         assert_eq!(map.get(&3), Some(&4));
+
+        // `7` wraps around to the first slot, and it stays there when the cluster before it
+        // shrinks, because the gap would be before its ideal slot.
+        map.insert(7, 8);
+        assert_eq!(slot_keys(&map), vec![Some(7), None, Some(2), Some(3)]);
+        assert_eq!(map.pop_front(), Some((2, 3)));
+        assert_eq!(slot_keys(&map), vec![Some(7), None, None, Some(3)]);
+        assert_eq!(map.get(&7), Some(&8));
     }
 
     #[test]
     fn test_map_pop() {
-        let mut map = OmniMap::new();
+        // The identity hasher places each key at the slot of its value.
+        let mut map = identity_map();
 
         // Pop when the map is empty must return None.
         assert_eq!(map.pop(), None);
@@ -477,93 +546,75 @@ mod map_tests {
         assert_eq!(key, 1);
         assert_eq!(value, 2);
         assert_eq!(map.len(), 0);
-        assert_eq!(map.debug_deleted(), 1);
-
-        assert!(map.debug_tag(0).is_deleted());
-
         assert_eq!(map.capacity(), 3);
 
+        // The slot of the removed entry must be freed.
+        assert_eq!(slot_keys(&map), vec![None; 4]);
+
         // Must return None, because the map is empty.
         assert_eq!(map.pop(), None);
-        assert_eq!(map.debug_deleted(), 1);
 
         // Insert new items.
         for i in 1..4 {
             map.insert(i, i + 1);
         }
 
-        // Now, the map must expand its capacity reset the deleted counter.
+        // The freed slot is reused without expanding the capacity.
         assert_eq!(map.len(), 3);
-        assert_eq!(map.debug_deleted(), 0);
-        assert_eq!(map.capacity(), 7);
+        assert_eq!(map.capacity(), 3);
+        assert_eq!(slot_keys(&map), vec![None, Some(1), Some(2), Some(3)]);
 
         // Pop the last item.
         assert_eq!(map.pop(), Some((3, 4)));
 
         // Map state at this point.
         assert_eq!(map.len(), 2);
-        assert_eq!(map.debug_deleted(), 1);
-        assert_eq!(map.capacity(), 7);
-
-        let mut deleted = 0;
-        let mut occupied = 0;
-        let mut empty = 0;
-
-        for i in 0..map.debug_allocated_cap() {
-            match map.debug_tag(i) {
-                Tag::Deleted => {
-                    deleted += 1;
-                }
-                Tag::Occupied => {
-                    occupied += 1;
-                }
-                Tag::Empty => {
-                    empty += 1;
-                }
-            }
-        }
-
-        // Expected index state at this point.
-        assert_eq!(deleted, 1);
-        assert_eq!(occupied, 2);
-        assert_eq!(empty, 5);
+        assert_eq!(map.capacity(), 3);
+        assert_eq!(slot_keys(&map), vec![None, Some(1), Some(2), None]);
 
         // Expected values at this point.
         assert_eq!(map.get(&1), Some(&2));
         assert_eq!(map.get(&2), Some(&3));
         assert_eq!(map.get(&3), None);
+
+        // `5` is displaced after `2`, and it moves back when the slot of `2` is freed.
+        map.insert(5, 6);
+        assert_eq!(slot_keys(&map), vec![None, Some(1), Some(2), Some(5)]);
+        assert_eq!(map.shift_remove(&2), Some(3));
+        assert_eq!(slot_keys(&map), vec![None, Some(1), Some(5), None]);
+        assert_eq!(map.pop(), Some((5, 6)));
+        assert_eq!(slot_keys(&map), vec![None, Some(1), None, None]);
     }
 
     #[test]
     fn test_map_clear() {
-        let mut map = OmniMap::with_capacity(4);
+        // The identity hasher places each key at the slot of its value.
+        let mut map =
+            OmniMap::with_capacity_and_hasher(4, BuildHasherDefault::<IdentityHasher>::default());
 
         for i in 1..4 {
             map.insert(i, i + 1);
         }
 
         assert_eq!(map.len(), 3);
-        assert_eq!(map.debug_deleted(), 0);
         assert_eq!(map.capacity(), 4);
+        assert_eq!(slot_keys(&map), vec![None, Some(1), Some(2), Some(3), None]);
 
         // Remove an item.
         map.shift_remove(&1);
 
         assert_eq!(map.len(), 2);
-        assert_eq!(map.debug_deleted(), 1);
         assert_eq!(map.capacity(), 4);
+        assert_eq!(slot_keys(&map), vec![None, None, Some(2), Some(3), None]);
 
         // Clear the map.
         map.clear();
 
         assert_eq!(map.len(), 0);
-        assert_eq!(map.debug_deleted(), 0);
         assert_eq!(map.capacity(), 4);
 
         // All slots must be empty in the index.
-        for i in 0..map.debug_allocated_cap() {
-            assert!(map.debug_tag(i).is_empty())
-        }
+        assert_eq!(slot_keys(&map), vec![None; 5]);
 
         // Reinserting items must work.
         map.insert(1, 2);
@@ -647,7 +698,7 @@ mod map_tests {
         map.shrink_to_fit();
 
         assert_eq!(map.len(), 10);
-        assert_eq!(map.debug_deleted(), 0);
+        assert_eq!(map.debug_occupied(), map.len());
         assert_eq!(map.capacity(), 10);
         assert_eq!(map.debug_allocated_cap(), 12);
 
@@ -669,7 +720,7 @@ mod map_tests {
         map.shrink_to_fit();
 
         assert_eq!(map.len(), 0);
-        assert_eq!(map.debug_deleted(), 0);
+        assert_eq!(map.debug_occupied(), map.len());
         assert_eq!(map.capacity(), 0);
         assert_eq!(map.debug_allocated_cap(), 0);
     }
@@ -685,7 +736,7 @@ mod map_tests {
         }
 
         assert_eq!(map.len(), 10);
-        assert_eq!(map.debug_deleted(), 0);
+        assert_eq!(map.debug_occupied(), map.len());
         assert_eq!(map.capacity(), 14);
         assert_eq!(map.debug_allocated_cap(), 16);
 
@@ -725,7 +776,7 @@ mod map_tests {
         map.shrink_to(0);
 
         assert_eq!(map.len(), 0);
-        assert_eq!(map.debug_deleted(), 0);
+        assert_eq!(map.debug_occupied(), map.len());
         assert_eq!(map.capacity(), 0);
         assert_eq!(map.debug_allocated_cap(), 0);
     }
@@ -871,7 +922,7 @@ mod map_tests {
 
         let mut into_iter = map.into_iter();
 
-        assert!(matches!(into_iter.next(), None));
+        assert!(into_iter.next().is_none());
 
         // Dropping the iterator should be fine.
         drop(into_iter);
@@ -947,7 +998,7 @@ mod map_tests {
 
         // Clone must be empty.
         assert_eq!(cloned.len(), 0);
-        assert_eq!(cloned.debug_deleted(), 0);
+        assert_eq!(cloned.debug_occupied(), cloned.len());
         assert_eq!(cloned.capacity(), 0);
     }
 
@@ -962,7 +1013,7 @@ mod map_tests {
 
         // Clone must have the same length and capacity as the original.
         assert_eq!(cloned.len(), original.len());
-        assert_eq!(cloned.debug_deleted(), original.debug_deleted());
+        assert_eq!(cloned.debug_occupied(), original.debug_occupied());
         assert_eq!(cloned.capacity(), original.capacity());
 
         // Entries in the clone must be the same as in the original.
@@ -989,7 +1040,7 @@ mod map_tests {
 
         // Clone must be empty.
         assert_eq!(cloned.len(), 0);
-        assert_eq!(cloned.debug_deleted(), 0);
+        assert_eq!(cloned.debug_occupied(), cloned.len());
         assert_eq!(cloned.capacity(), 0);
     }
 
@@ -1009,8 +1060,8 @@ mod map_tests {
         // Clone must have the same length as the original.
         assert_eq!(cloned.len(), original.len());
 
-        // Deleted slots must be removed in the clone.
-        assert_ne!(cloned.debug_deleted(), original.debug_deleted());
+        // The clone must be reindexed.
+        assert_eq!(cloned.debug_occupied(), cloned.len());
 
         // Clone must have a capacity equal to the length of the original.
         assert_eq!(cloned.capacity(), original.len());
@@ -1078,7 +1129,7 @@ mod map_tests {
         }
 
         assert_eq!(map.len(), 0);
-        assert_eq!(map.debug_deleted(), 0);
+        assert_eq!(map.debug_occupied(), map.len());
         assert_eq!(map.capacity(), 100);
         assert_eq!(map.debug_allocated_cap(), 115);
 
@@ -1099,13 +1150,12 @@ mod map_tests {
         // Collect slots' information.
         let mut occupied_indices = std::collections::HashSet::new();
         let mut empty_indices = 0;
-        let mut deleted_indices = 0;
 
         let alloc_cap = map.debug_allocated_cap();
 
         for i in 0..alloc_cap {
             match map.debug_tag(i) {
-                Tag::Occupied => {
+                tag if tag.is_occupied() => {
                     let index = map.debug_slot_value(i);
                     assert!(
                         occupied_indices.insert(index),
@@ -1113,33 +1163,24 @@ mod map_tests {
                         index
                     );
                 }
-                Tag::Empty => {
+                _ => {
                     empty_indices += 1;
                 }
-                Tag::Deleted => {
-                    deleted_indices += 1;
-                }
             }
         }
 
         // Check integrity.
         assert_eq!(occupied_indices.len(), 75);
-        assert_eq!(deleted_indices, 25);
         assert_eq!(
             empty_indices,
-            map.debug_allocated_cap() - (occupied_indices.len() + deleted_indices)
+            map.debug_allocated_cap() - occupied_indices.len()
         );
 
         // Compact the map to reindex.
         map.shrink_to_fit();
 
-        // No deleted slots should be present.
-        for i in 0..map.debug_allocated_cap() {
-            assert!(!map.debug_tag(i).is_deleted())
-        }
-
         assert_eq!(map.len(), 75);
-        assert_eq!(map.debug_deleted(), 0);
+        assert_eq!(map.debug_occupied(), map.len());
         assert_eq!(map.capacity(), 75);
         assert_eq!(map.debug_allocated_cap(), 86);
 
@@ -1161,13 +1202,12 @@ mod map_tests {
             map.shift_remove(&i);
         }
 
-        // No occupied or empty slots should be present.
+        // All slots must be empty.
         for i in 0..map.debug_allocated_cap() {
-            assert!(!map.debug_tag(i).is_occupied())
+            assert!(map.debug_tag(i).is_empty())
         }
 
         assert_eq!(map.len(), 0);
-        assert_eq!(map.debug_deleted(), 75);
         assert_eq!(map.capacity(), 75);
         assert_eq!(map.debug_allocated_cap(), 86);
 
@@ -1175,24 +1215,19 @@ mod map_tests {
             map.insert(i, i);
         }
 
-        // Up to 75, the map must reindex and reuse deleted slots without new allocation.
+        // Up to 75, the map must reuse the freed slots without new allocation.
         assert_eq!(map.len(), 75);
-        assert_eq!(map.debug_deleted(), 0);
+        assert_eq!(map.debug_occupied(), map.len());
         assert_eq!(map.capacity(), 75);
         assert_eq!(map.debug_allocated_cap(), 86);
 
-        // The map must be able to reindex successfully, no deleted slots should be present.
-        for i in 0..map.debug_allocated_cap() {
-            assert!(!map.debug_tag(i).is_deleted())
-        }
-
         for i in 75..100 {
             map.insert(i, i);
         }
 
         // From 75, the map must reallocate.
         assert_eq!(map.len(), 100);
-        assert_eq!(map.debug_deleted(), 0);
+        assert_eq!(map.debug_occupied(), map.len());
         assert_eq!(map.capacity(), 112);
         assert_eq!(map.debug_allocated_cap(), 128);
 
@@ -1236,4 +1271,3082 @@ mod map_tests {
         // Capacity goes back to 0.
         assert_eq!(map.capacity(), 0);
     }
+
+    #[test]
+    fn test_map_entry_or_insert() {
+        let mut map = OmniMap::new();
+
+        // Vacant entry.
+        assert_eq!(*map.entry(1).or_insert(10), 10);
+
+        // Occupied entry, the default is ignored.
+        assert_eq!(*map.entry(1).or_insert(20), 10);
+
+        *map.entry(2).or_insert_with(|| 20) += 1;
+        *map.entry(3).or_insert_with_key(|key| key * 10) += 1;
+        *map.entry(4).or_default() += 41;
+
+        assert_eq!(map.len(), 4);
+        assert_eq!(
+            map.iter().collect::<Vec<(&u8, &u8)>>(),
+            vec![(&1, &10), (&2, &21), (&3, &31), (&4, &41)]
+        );
+    }
+
+    #[test]
+    fn test_map_entry_and_modify() {
+        let mut map = OmniMap::new();
+
+        // Vacant, the closure must not be called.
+        map.entry("a").and_modify(|v| *v += 1).or_insert(1);
+        assert_eq!(map.get(&"a"), Some(&1));
+
+        // Occupied.
+        map.entry("a").and_modify(|v| *v += 1).or_insert(1);
+        assert_eq!(map.get(&"a"), Some(&2));
+    }
+
+    #[test]
+    fn test_map_entry_index() {
+        let mut map = OmniMap::new();
+
+        for i in 0..5 {
+            map.insert(i, i);
+        }
+
+        match map.entry(3) {
+            Entry::Occupied(entry) => {
+                assert_eq!(entry.index(), 3);
+                assert_eq!(entry.key(), &3);
+                assert_eq!(entry.get(), &3);
+            }
+            Entry::Vacant(_) => panic!("Entry must be occupied"),
+        }
+
+        match map.entry(10) {
+            Entry::Occupied(_) => panic!("Entry must be vacant"),
+            Entry::Vacant(entry) => {
+                // The index the entry will have after insertion.
+                assert_eq!(entry.index(), 5);
+                assert_eq!(entry.key(), &10);
+                assert_eq!(*entry.insert(100), 100);
+            }
+        }
+
+        assert_eq!(map.len(), 6);
+        assert_eq!(map[5], 100);
+    }
+
+    #[test]
+    fn test_map_entry_occupied_insert() {
+        let mut map = OmniMap::new();
+
+        map.insert(1, "a");
+
+        if let Entry::Occupied(mut entry) = map.entry(1) {
+            assert_eq!(entry.insert("b"), "a");
+            *entry.get_mut() = "c";
+        }
+
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(&1), Some(&"c"));
+    }
+
+    #[test]
+    fn test_map_entry_shift_remove() {
+        // The identity hasher places each key at the slot of its value.
+        let mut map = identity_map();
+
+        for i in 1..5 {
+            map.insert(i, i + 1);
+        }
+
+        if let Entry::Occupied(entry) = map.entry(2) {
+            assert_eq!(entry.shift_remove_entry(), (2, 3));
+        }
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(
+            slot_keys(&map),
+            vec![None, Some(1), None, Some(3), Some(4), None, None, None]
+        );
+        assert_eq!(map.get(&2), None);
+
+        // Order must be preserved.
+        assert_eq!(
+            map.iter().collect::<Vec<(&u8, &u8)>>(),
+            vec![(&1, &2), (&3, &4), (&4, &5)]
+        );
+
+        // The index must have been updated.
+        for i in [1, 3, 4] {
+            assert_eq!(map.get(&i), Some(&(i + 1)));
+        }
+    }
+
+    #[test]
+    fn test_map_entry_swap_remove() {
+        let mut map = OmniMap::new();
+
+        for i in 1..5 {
+            map.insert(i, i + 1);
+        }
+
+        if let Entry::Occupied(entry) = map.entry(1) {
+            assert_eq!(entry.swap_remove(), 2);
+        }
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(&1), None);
+
+        // The last entry takes the place of the removed entry.
+        assert_eq!(
+            map.iter().collect::<Vec<(&u8, &u8)>>(),
+            vec![(&4, &5), (&2, &3), (&3, &4)]
+        );
+
+        for i in 2..5 {
+            assert_eq!(map.get(&i), Some(&(i + 1)));
+        }
+    }
+
+    #[test]
+    fn test_map_entry_reserve() {
+        let mut map = OmniMap::new();
+
+        // Creating entries must allocate as needed.
+        for i in 0..100 {
+            map.entry(i).or_insert(i);
+        }
+
+        assert_eq!(map.len(), 100);
+
+        for i in 0..100 {
+            assert_eq!(map.get(&i), Some(&i));
+        }
+    }
+
+    #[test]
+    fn test_map_borrowed_lookup() {
+        let mut map: OmniMap<String, u8> = OmniMap::new();
+
+        for (i, key) in ["a", "b", "c", "d"].into_iter().enumerate() {
+            map.insert(key.to_string(), i as u8);
+        }
+
+        // Lookups with `&str` must hash and compare the same as with `&String`.
+        assert_eq!(map.get("a"), Some(&0));
+        assert_eq!(map.get(&"a".to_string()), Some(&0));
+        assert!(map.contains_key("b"));
+        assert!(!map.contains_key("x"));
+
+        if let Some(value) = map.get_mut("c") {
+            *value = 20;
+        }
+        assert_eq!(map.get("c"), Some(&20));
+
+        assert_eq!(map.shift_remove("a"), Some(0));
+        assert_eq!(map.swap_remove("b"), Some(1));
+        assert_eq!(map.shift_remove("x"), None);
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("c"), Some(&20));
+        assert_eq!(map.get("d"), Some(&3));
+    }
+
+    #[test]
+    fn test_map_borrowed_lookup_boxed() {
+        let mut map: OmniMap<Box<[u8]>, u8> = OmniMap::new();
+
+        map.insert(Box::new([1, 2]), 1);
+        map.insert(Box::new([3]), 2);
+
+        // Unsized borrowed form.
+        assert_eq!(map.get(&[1, 2][..]), Some(&1));
+        assert_eq!(map.get(&[3][..]), Some(&2));
+        assert_eq!(map.get(&[4][..]), None);
+    }
+
+    /// A hasher that maps every key to the same hash value to force maximal collisions.
+    #[derive(Default)]
+    struct CollidingHasher;
+
+    impl Hasher for CollidingHasher {
+        fn finish(&self) -> u64 {
+            7
+        }
+
+        fn write(&mut self, _: &[u8]) {}
+    }
+
+    /// A hasher that uses the written integer as the hash value to control the ideal slots.
+    #[derive(Default)]
+    struct IdentityHasher(u64);
+
+    impl Hasher for IdentityHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            // Other integers are folded byte by byte, so that small keys keep their value.
+            for &byte in bytes {
+                self.0 = (self.0 << 8) | byte as u64;
+            }
+        }
+
+        fn write_u32(&mut self, i: u32) {
+            self.0 = i as u64;
+        }
+    }
+
+    /// Returns a new empty map that uses [`IdentityHasher`].
+    fn identity_map<K, V>() -> OmniMap<K, V, BuildHasherDefault<IdentityHasher>> {
+        OmniMap::with_hasher(BuildHasherDefault::default())
+    }
+
+    /// Returns the key of the entry referenced by each slot of the index, or `None` for the empty
+    /// slots.
+    fn slot_keys<K: Copy + Eq + Hash, V, S: BuildHasher>(map: &OmniMap<K, V, S>) -> Vec<Option<K>> {
+        (0..map.debug_allocated_cap())
+            .map(|slot| {
+                map.debug_tag(slot).is_occupied().then(|| {
+                    let (key, _) = map.get_index(map.debug_slot_value(slot)).unwrap();
+                    *key
+                })
+            })
+            .collect()
+    }
+
+    /// A hash builder with state, that counts the built hashers.
+    #[derive(Clone, Default)]
+    struct CountingState {
+        built: Rc<RefCell<usize>>,
+    }
+
+    impl BuildHasher for CountingState {
+        type Hasher = std::collections::hash_map::DefaultHasher;
+
+        fn build_hasher(&self) -> Self::Hasher {
+            *self.built.borrow_mut() += 1;
+            Self::Hasher::new()
+        }
+    }
+
+    #[test]
+    fn test_map_with_hasher() {
+        let state = CountingState::default();
+
+        let mut map = OmniMap::with_hasher(state.clone());
+
+        assert_eq!(map.capacity(), 0);
+
+        map.insert(1, "a");
+        map.insert(2, "b");
+
+        assert_eq!(*map.hasher().built.borrow(), 2);
+
+        assert_eq!(map.get(&1), Some(&"a"));
+        assert_eq!(map.get(&2), Some(&"b"));
+
+        // Each lookup hashes the key exactly once.
+        assert_eq!(*state.built.borrow(), 4);
+    }
+
+    #[test]
+    fn test_map_with_capacity_and_hasher() {
+        let map: OmniMap<u8, u8, _> =
+            OmniMap::with_capacity_and_hasher(10, CountingState::default());
+
+        assert_eq!(map.len(), 0);
+        assert_eq!(map.capacity(), 10);
+        assert_eq!(map.debug_allocated_cap(), 12);
+
+        let map: OmniMap<u8, u8, _> =
+            OmniMap::with_capacity_and_hasher(0, CountingState::default());
+
+        assert_eq!(map.capacity(), 0);
+    }
+
+    #[test]
+    fn test_map_hasher_default_clone() {
+        let mut map: OmniMap<u8, u8, CountingState> = OmniMap::default();
+
+        assert_eq!(map.capacity(), 16);
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        let cloned = map.clone();
+        let compact = map.clone_compact();
+
+        // Clones must share the same hash builder to find the copied slots.
+        assert!(Rc::ptr_eq(&map.hasher().built, &cloned.hasher().built));
+        assert!(Rc::ptr_eq(&map.hasher().built, &compact.hasher().built));
+
+        for i in 0..10 {
+            assert_eq!(cloned.get(&i), Some(&i));
+            assert_eq!(compact.get(&i), Some(&i));
+        }
+
+        assert_eq!(map, cloned);
+    }
+
+    #[test]
+    fn test_map_hasher_collisions() {
+        let mut map: OmniMap<u32, u32, BuildHasherDefault<CollidingHasher>> = OmniMap::default();
+
+        for i in 0..100 {
+            map.insert(i, i);
+        }
+
+        for i in (0..100).step_by(2) {
+            assert_eq!(map.shift_remove(&i), Some(i));
+        }
+
+        for i in 0..100 {
+            let expected = if i % 2 == 0 { None } else { Some(&i) };
+            assert_eq!(map.get(&i), expected);
+        }
+
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            (1..100).step_by(2).collect::<Vec<u32>>()
+        );
+    }
+
+    #[test]
+    fn test_map_backward_shift_deletion() {
+        let mut map: OmniMap<u32, u32, BuildHasherDefault<IdentityHasher>> =
+            OmniMap::with_capacity_and_hasher(6, BuildHasherDefault::default());
+
+        let cap = map.debug_allocated_cap() as u32;
+
+        // A cluster that wraps around the end of the index:
+        // [cap - 2, cap - 1, 2cap - 2, 2cap - 1, 0], where the last three are displaced.
+        let keys = [cap - 2, cap - 1, 2 * cap - 2, 2 * cap - 1, 0];
+        for key in keys {
+            map.insert(key, key);
+        }
+
+        // Removing the head of the cluster shifts the displaced slots back:
+        // [2cap - 2, cap - 1, 2cap - 1, 0, _].
+        assert_eq!(map.shift_remove(&(cap - 2)), Some(cap - 2));
+        assert_eq!(map.debug_occupied(), map.len());
+        assert!(map.debug_tag(1).is_occupied());
+        assert!(map.debug_tag(2).is_empty());
+
+        for key in &keys[1..] {
+            assert_eq!(map.get(key), Some(key));
+        }
+
+        // The following slots are not shifted before their ideal slots:
+        // [_, cap - 1, 2cap - 1, 0, _].
+        assert_eq!(map.swap_remove(&(2 * cap - 2)), Some(2 * cap - 2));
+        assert_eq!(map.debug_occupied(), map.len());
+        assert!(map.debug_tag(cap as usize - 2).is_empty());
+        assert!(map.debug_tag(cap as usize - 1).is_occupied());
+        assert!(map.debug_tag(0).is_occupied());
+        assert!(map.debug_tag(1).is_occupied());
+        assert_eq!(map.get(&(2 * cap - 1)), Some(&(2 * cap - 1)));
+        assert_eq!(map.get(&0), Some(&0));
+
+        // Entries: [cap - 1, 0, 2cap - 1].
+        assert_eq!(map.pop(), Some((2 * cap - 1, 2 * cap - 1)));
+        assert_eq!(map.pop_front(), Some((cap - 1, cap - 1)));
+        assert_eq!(map.get(&0), Some(&0));
+        assert_eq!(map.debug_occupied(), 1);
+
+        assert_eq!(map.pop(), Some((0, 0)));
+        for i in 0..map.debug_allocated_cap() {
+            assert!(map.debug_tag(i).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_map_backward_shift_deletion_mixed() {
+        // The identity hasher with small keys makes long clusters.
+        let mut map: OmniMap<u32, u32, BuildHasherDefault<IdentityHasher>> = OmniMap::default();
+        let mut expected: Vec<u32> = Vec::new();
+
+        // Deterministic pseudo-random sequence.
+        let mut state = 0x2545_f491_u32;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+
+        for _ in 0..5000 {
+            let key = next() % 256;
+            match next() % 5 {
+                0 | 1 => {
+                    if map.insert(key, key).is_none() {
+                        expected.push(key);
+                    }
+                }
+                2 => {
+                    let removed = map.shift_remove(&key);
+                    let position = expected.iter().position(|&k| k == key);
+                    assert_eq!(removed.is_some(), position.is_some());
+                    if let Some(position) = position {
+                        expected.remove(position);
+                    }
+                }
+                3 => {
+                    let removed = map.swap_remove(&key);
+                    let position = expected.iter().position(|&k| k == key);
+                    assert_eq!(removed.is_some(), position.is_some());
+                    if let Some(position) = position {
+                        expected.swap_remove(position);
+                    }
+                }
+                _ => {
+                    if key % 2 == 0 {
+                        assert_eq!(map.pop().map(|(k, _)| k), expected.pop());
+                    } else if !expected.is_empty() {
+                        assert_eq!(map.pop_front().map(|(k, _)| k), Some(expected.remove(0)));
+                    }
+                }
+            }
+
+            assert_eq!(map.len(), expected.len());
+        }
+
+        assert_eq!(map.iter_keys().copied().collect::<Vec<u32>>(), expected);
+        assert_eq!(map.debug_occupied(), map.len());
+        assert_positions(&map);
+
+        for key in 0..256 {
+            assert_eq!(map.contains_key(&key), expected.contains(&key));
+        }
+    }
+
+    #[test]
+    fn test_map_fingerprints() {
+        let mut map: OmniMap<u32, u32> = OmniMap::with_seed(42);
+
+        for i in 0..1000 {
+            map.insert(i, i);
+        }
+
+        for i in (0..1000).step_by(3) {
+            map.swap_remove(&i);
+        }
+
+        // Every occupied slot holds the fingerprint of its entry, also after shifting.
+        for slot in 0..map.debug_allocated_cap() {
+            let tag = map.debug_tag(slot);
+            if tag.is_occupied() {
+                let (key, _) = map.get_index(map.debug_slot_value(slot)).unwrap();
+                let hash = map.hasher().hash_one(key) as usize;
+                assert_eq!(tag, Tag::full(hash));
+            }
+        }
+    }
+
+    #[test]
+    fn test_map_group_probing_wraparound() {
+        // Capacities smaller and larger than a group.
+        for capacity in [1, 3, 7, 30, 100] {
+            let mut map: OmniMap<u32, u32, BuildHasherDefault<IdentityHasher>> =
+                OmniMap::with_capacity_and_hasher(capacity, BuildHasherDefault::default());
+
+            let cap = map.debug_allocated_cap() as u32;
+            let count = map.capacity() as u32;
+
+            // All keys have the last slot as the ideal slot and the same fingerprint, so the
+            // cluster wraps around and every probe must compare the keys.
+            let keys: Vec<u32> = (0..count).map(|i| cap - 1 + i * cap).collect();
+            for &key in &keys {
+                map.insert(key, key);
+            }
+
+            assert_eq!(map.debug_allocated_cap() as u32, cap);
+            for key in &keys {
+                assert_eq!(map.get(key), Some(key));
+            }
+
+            // Misses in the same cluster and in other slots.
+            assert_eq!(map.get(&(cap - 1 + count * cap)), None);
+            assert_eq!(map.get(&0), None);
+
+            for key in keys.iter().step_by(2) {
+                assert_eq!(map.shift_remove(key), Some(*key));
+            }
+
+            for (i, key) in keys.iter().enumerate() {
+                let expected = if i % 2 == 0 { None } else { Some(key) };
+                assert_eq!(map.get(key), expected);
+            }
+
+            assert_eq!(map.debug_occupied(), map.len());
+            assert_positions(&map);
+        }
+    }
+
+    #[test]
+    fn test_map_with_seed() {
+        let mut a = OmniMap::with_seed(42);
+        let mut b = OmniMap::with_seed(42);
+
+        for i in 0..100 {
+            a.insert(i, i);
+            b.insert(i, i);
+        }
+
+        // Maps with the same seed must have the same layout.
+        assert_eq!(a.debug_allocated_cap(), b.debug_allocated_cap());
+        for i in 0..a.debug_allocated_cap() {
+            assert_eq!(a.debug_tag(i), b.debug_tag(i));
+            if a.debug_tag(i).is_occupied() {
+                assert_eq!(a.debug_slot_value(i), b.debug_slot_value(i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_map_random_seed() {
+        let a: OmniMap<u32, u32> = OmniMap::new();
+        let b: OmniMap<u32, u32> = OmniMap::new();
+
+        // Every map must have its own keys.
+        assert_ne!(a.hasher().hash_one(1), b.hasher().hash_one(1));
+
+        // Clones must keep the keys of the source.
+        let c = a.clone();
+        assert_eq!(a.hasher().hash_one(1), c.hasher().hash_one(1));
+    }
+
+    /// An allocator that forwards to the global allocator and counts the live allocations.
+    #[derive(Clone, Default)]
+    struct CountingAlloc {
+        live: Rc<Cell<isize>>,
+        total: Rc<Cell<usize>>,
+    }
+
+    unsafe impl Allocator for CountingAlloc {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+            self.live.set(self.live.get() + 1);
+            self.total.set(self.total.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.live.set(self.live.get() - 1);
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    /// A bump allocator with a fixed buffer that releases its memory only when dropped.
+    struct Arena {
+        buffer: [Cell<u64>; 512],
+        used: Cell<usize>,
+    }
+
+    impl Arena {
+        fn new() -> Self {
+            Arena {
+                buffer: [const { Cell::new(0) }; 512],
+                used: Cell::new(0),
+            }
+        }
+    }
+
+    unsafe impl Allocator for &Arena {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+            assert!(layout.align() <= align_of::<u64>());
+            let start = self.used.get();
+            let end = start + layout.size().div_ceil(size_of::<u64>());
+            if end > self.buffer.len() {
+                return Err(AllocError::AllocatorErr);
+            }
+            self.used.set(end);
+            Ok(NonNull::new(self.buffer[start..].as_ptr() as *mut u8).unwrap())
+        }
+
+        unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+    }
+
+    #[test]
+    fn test_map_new_in() {
+        let alloc = CountingAlloc::default();
+
+        let mut map = OmniMap::new_in(alloc.clone());
+
+        // No allocation before the first insert.
+        assert_eq!(alloc.total.get(), 0);
+
+        for i in 0..100 {
+            map.insert(i, i);
+        }
+
+        // The entries and the index.
+        assert_eq!(alloc.live.get(), 2);
+        assert!(alloc.total.get() > 2);
+
+        for i in 0..100 {
+            assert_eq!(map.get(&i), Some(&i));
+        }
+
+        map.shrink_to_fit();
+        assert_eq!(alloc.live.get(), 2);
+
+        let clone = map.clone();
+        assert_eq!(alloc.live.get(), 4);
+
+        drop(map);
+        assert_eq!(alloc.live.get(), 2);
+
+        drop(clone);
+        assert_eq!(alloc.live.get(), 0);
+    }
+
+    #[test]
+    fn test_map_with_capacity_in() {
+        let alloc = CountingAlloc::default();
+
+        let map: OmniMap<u8, u8, _, _> = OmniMap::with_capacity_in(10, alloc.clone());
+
+        assert_eq!(map.capacity(), 10);
+        assert_eq!(alloc.live.get(), 2);
+
+        drop(map);
+        assert_eq!(alloc.live.get(), 0);
+    }
+
+    #[test]
+    fn test_map_allocator_into_iter() {
+        let alloc = CountingAlloc::default();
+
+        let mut map = OmniMap::with_hasher_in(RandomState::with_seed(0), alloc.clone());
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        let mut iter = map.into_iter();
+
+        // The index is deallocated, the entries are owned by the iterator.
+        assert_eq!(alloc.live.get(), 1);
+
+        assert_eq!(iter.next(), Some((0, 0)));
+
+        drop(iter);
+        assert_eq!(alloc.live.get(), 0);
+
+        // Allocated but empty.
+        let map: OmniMap<u8, u8, _, _> = OmniMap::with_capacity_in(10, alloc.clone());
+        assert_eq!(alloc.live.get(), 2);
+
+        let mut iter = map.into_iter();
+        assert_eq!(iter.next(), None);
+
+        drop(iter);
+        assert_eq!(alloc.live.get(), 0);
+    }
+
+    #[test]
+    fn test_map_allocator_arena() {
+        let arena = Arena::new();
+
+        let mut a = OmniMap::new_in(&arena);
+        let mut b = OmniMap::new_in(&arena);
+
+        for i in 0..10_u64 {
+            a.insert(i, i * 2);
+            b.insert(i * 2, i);
+        }
+
+        for i in 0..10_u64 {
+            assert_eq!(a.get(&i), Some(&(i * 2)));
+            assert_eq!(b.get(&(i * 2)), Some(&i));
+        }
+
+        assert!(arena.used.get() > 0);
+    }
+
+    #[test]
+    fn test_map_allocator_error() {
+        let arena = Arena::new();
+
+        let mut map: OmniMap<u64, u64, _, _> = OmniMap::new_in(&arena);
+
+        // The arena is too small for this capacity.
+        assert!(matches!(
+            map.try_reserve(1000),
+            Err(AllocError::AllocatorErr)
+        ));
+
+        // The map must remain usable.
+        assert_eq!(map.capacity(), 0);
+        map.insert(1, 1);
+        assert_eq!(map.get(&1), Some(&1));
+    }
+
+    #[cfg(feature = "allocator_api")]
+    #[test]
+    fn test_map_allocator_api() {
+        let mut map = OmniMap::new_in(std::alloc::System);
+
+        for i in 0..100 {
+            map.insert(i, i);
+        }
+
+        for i in 0..100 {
+            assert_eq!(map.get(&i), Some(&i));
+        }
+    }
+
+    fn assert_send<T: Send>() {}
+
+    fn assert_sync<T: Sync>() {}
+
+    /// Fails to compile if the type implements the trait.
+    ///
+    /// If it does, both impls of `AmbiguousIfImpl` apply and the type of `_` can't be inferred.
+    macro_rules! assert_not_impl {
+        ($ty:ty, $trait:path) => {
+            const _: fn() = || {
+                trait AmbiguousIfImpl<A> {
+                    fn some_item() {}
+                }
+
+                impl<T: ?Sized> AmbiguousIfImpl<()> for T {}
+
+                struct Invalid;
+
+                impl<T: ?Sized + $trait> AmbiguousIfImpl<Invalid> for T {}
+
+                let _ = <$ty as AmbiguousIfImpl<_>>::some_item;
+            };
+        };
+    }
+
+    #[test]
+    fn test_map_send_sync() {
+        assert_send::<OmniMap<String, Vec<u8>>>();
+        assert_sync::<OmniMap<String, Vec<u8>>>();
+
+        assert_send::<OmniMapIterator<String, Vec<u8>>>();
+        assert_sync::<OmniMapIterator<String, Vec<u8>>>();
+
+        assert_send::<Entry<'_, String, Vec<u8>>>();
+        assert_sync::<Entry<'_, String, Vec<u8>>>();
+
+        assert_send::<OmniMap<u8, u8, BuildHasherDefault<CollidingHasher>, Global>>();
+        assert_sync::<OmniMap<u8, u8, BuildHasherDefault<CollidingHasher>, Global>>();
+
+        // The auto traits must not be implemented for types that aren't thread-safe.
+        assert_not_impl!(OmniMap<Rc<u8>, u8>, Send);
+        assert_not_impl!(OmniMap<Rc<u8>, u8>, Sync);
+        assert_not_impl!(OmniMap<u8, Rc<u8>>, Send);
+        assert_not_impl!(OmniMap<Cell<u8>, u8>, Sync);
+        assert_not_impl!(OmniMap<u8, Cell<u8>>, Sync);
+
+        assert_not_impl!(OmniMapIterator<Rc<u8>, u8>, Send);
+        assert_not_impl!(OmniMapIterator<u8, Rc<u8>>, Sync);
+        assert_not_impl!(OmniMapIterator<Cell<u8>, u8>, Sync);
+    }
+
+    #[test]
+    fn test_map_send_to_thread() {
+        let mut map = OmniMap::new();
+
+        for i in 0..100 {
+            map.insert(i, i.to_string());
+        }
+
+        let map = std::thread::spawn(move || {
+            map.insert(100, "100".to_string());
+            map
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(map.len(), 101);
+        assert_eq!(map.get(&100), Some(&"100".to_string()));
+    }
+
+    #[test]
+    fn test_map_share_between_threads() {
+        let mut map = OmniMap::new();
+
+        for i in 0..100 {
+            map.insert(i, i * 2);
+        }
+
+        let map = std::sync::Arc::new(map);
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let map = map.clone();
+                std::thread::spawn(move || (0..100).all(|i| map.get(&i) == Some(&(i * 2))))
+            })
+            .collect();
+
+        for handle in handles {
+            assert!(handle.join().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_map_get_index() {
+        let mut map = OmniMap::new();
+
+        assert_eq!(map.get_index(0), None);
+        assert_eq!(map.get_index_mut(0), None);
+
+        for i in 0..10 {
+            map.insert(i, i * 10);
+        }
+
+        for i in 0..10 {
+            assert_eq!(map.get_index(i), Some((&i, &(i * 10))));
+        }
+
+        assert_eq!(map.get_index(10), None);
+
+        // Mutate through the position.
+        let (key, value) = map.get_index_mut(3).unwrap();
+        assert_eq!(*key, 3);
+        *value = 333;
+        assert_eq!(map.get(&3), Some(&333));
+        assert_eq!(map.get_index_mut(10), None);
+
+        // Positions follow the removal mode.
+        map.shift_remove(&0);
+        assert_eq!(map.get_index(0), Some((&1, &10)));
+        assert_eq!(map.get_index(9), None);
+
+        map.swap_remove(&1);
+        assert_eq!(map.get_index(0), Some((&9, &90)));
+    }
+
+    #[test]
+    fn test_map_get_index_of() {
+        let mut map = OmniMap::new();
+
+        assert_eq!(map.get_index_of(&0), None);
+
+        for i in 0..100 {
+            map.insert(i, i);
+        }
+
+        for i in 0..100 {
+            assert_eq!(map.get_index_of(&i), Some(i));
+        }
+
+        assert_eq!(map.get_index_of(&100), None);
+
+        // Indices are shifted after the removed entry.
+        map.shift_remove(&50);
+
+        assert_eq!(map.get_index_of(&49), Some(49));
+        assert_eq!(map.get_index_of(&50), None);
+        assert_eq!(map.get_index_of(&51), Some(50));
+        assert_eq!(map.get_index_of(&99), Some(98));
+
+        // The last entry takes the place of the removed entry.
+        map.swap_remove(&0);
+
+        assert_eq!(map.get_index_of(&99), Some(0));
+
+        for i in 0..map.len() {
+            let (key, _) = map.get_index(i).unwrap();
+            assert_eq!(map.get_index_of(key), Some(i));
+        }
+    }
+
+    #[test]
+    fn test_map_get_full() {
+        let mut map: OmniMap<String, u32> = OmniMap::new();
+
+        assert_eq!(map.get_full("a"), None);
+        assert_eq!(map.get_key_value("a"), None);
+
+        map.insert("a".to_string(), 1);
+        map.insert("b".to_string(), 2);
+
+        assert_eq!(map.get_full("a"), Some((0, &"a".to_string(), &1)));
+        assert_eq!(map.get_full("b"), Some((1, &"b".to_string(), &2)));
+        assert_eq!(map.get_full("c"), None);
+
+        assert_eq!(map.get_key_value("b"), Some((&"b".to_string(), &2)));
+        assert_eq!(map.get_key_value("c"), None);
+    }
+
+    /// Checks that every entry can be found at its position.
+    fn assert_positions<S: BuildHasher>(map: &OmniMap<u32, u32, S>) {
+        for i in 0..map.len() {
+            let (key, _) = map.get_index(i).unwrap();
+            assert_eq!(map.get_index_of(key), Some(i));
+        }
+    }
+
+    #[test]
+    fn test_map_insert_full() {
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            assert_eq!(map.insert_full(i, i), (i as usize, None));
+        }
+
+        assert_eq!(map.insert_full(5, 50), (5, Some(5)));
+        assert_eq!(map.len(), 10);
+        assert_eq!(map.get(&5), Some(&50));
+
+        map.shift_remove(&0);
+        assert_eq!(map.insert_full(0, 0), (9, None));
+    }
+
+    #[test]
+    fn test_map_shift_insert() {
+        let mut map = OmniMap::new();
+
+        // Empty map.
+        assert_eq!(map.shift_insert(0, 1, 1), None);
+
+        // Front, middle and end.
+        assert_eq!(map.shift_insert(0, 0, 0), None);
+        assert_eq!(map.shift_insert(2, 3, 3), None);
+        assert_eq!(map.shift_insert(2, 2, 2), None);
+
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![0, 1, 2, 3]
+        );
+        assert_positions(&map);
+
+        // Large shifts use linear search, small shifts use the hashes.
+        for i in 4..100 {
+            map.insert(i, i);
+        }
+
+        assert_eq!(map.shift_insert(0, 1000, 1000), None);
+        assert_eq!(map.shift_insert(99, 1001, 1001), None);
+        assert_eq!(map.shift_insert(50, 1002, 1002), None);
+
+        assert_eq!(map.len(), 103);
+        assert_eq!(map.get_index(0), Some((&1000, &1000)));
+        assert_eq!(map.get_index(50), Some((&1002, &1002)));
+        assert_eq!(map.get_index(100), Some((&1001, &1001)));
+        assert_eq!(map.get_index(102), Some((&99, &99)));
+        assert_positions(&map);
+
+        for i in 0..100 {
+            assert_eq!(map.get(&i), Some(&i));
+        }
+    }
+
+    #[test]
+    fn test_map_shift_insert_existing() {
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        // Move forward.
+        assert_eq!(map.shift_insert(7, 2, 20), Some(2));
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![0, 1, 3, 4, 5, 6, 7, 2, 8, 9]
+        );
+
+        // Move backward.
+        assert_eq!(map.shift_insert(0, 9, 90), Some(9));
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![9, 0, 1, 3, 4, 5, 6, 7, 2, 8]
+        );
+
+        // Same position.
+        assert_eq!(map.shift_insert(1, 0, 100), Some(0));
+        assert_eq!(map.get_index(1), Some((&0, &100)));
+
+        assert_eq!(map.len(), 10);
+        assert_eq!(map.get(&2), Some(&20));
+        assert_eq!(map.get(&9), Some(&90));
+        assert_positions(&map);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds.")]
+    fn test_map_shift_insert_out_of_bounds() {
+        let mut map = OmniMap::new();
+        map.insert(1, 1);
+        map.shift_insert(2, 2, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds.")]
+    fn test_map_shift_insert_existing_out_of_bounds() {
+        let mut map = OmniMap::new();
+        map.insert(1, 1);
+        map.shift_insert(1, 1, 1);
+    }
+
+    #[test]
+    fn test_map_insert_before_after() {
+        let mut map = OmniMap::new();
+
+        // No anchor.
+        assert_eq!(map.insert_before(&0, 1, 1), Err((1, 1)));
+        assert_eq!(map.insert_after(&0, 1, 1), Err((1, 1)));
+        assert!(map.is_empty());
+
+        map.insert(0, 0);
+        map.insert(5, 5);
+
+        assert_eq!(map.insert_after(&0, 1, 1), Ok(None));
+        assert_eq!(map.insert_before(&5, 4, 4), Ok(None));
+        assert_eq!(map.insert_after(&1, 2, 2), Ok(None));
+        assert_eq!(map.insert_before(&4, 3, 3), Ok(None));
+        assert_eq!(map.insert_after(&5, 6, 6), Ok(None));
+
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![0, 1, 2, 3, 4, 5, 6]
+        );
+        assert_positions(&map);
+
+        // Move existing keys.
+        assert_eq!(map.insert_before(&0, 6, 60), Ok(Some(6)));
+        assert_eq!(map.insert_after(&5, 1, 10), Ok(Some(1)));
+        assert_eq!(map.insert_before(&3, 4, 40), Ok(Some(4)));
+        assert_eq!(map.insert_after(&2, 0, 0), Ok(Some(0)));
+
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![6, 2, 0, 4, 3, 5, 1]
+        );
+
+        // The key is the anchor itself.
+        assert_eq!(map.insert_before(&3, 3, 30), Ok(Some(3)));
+        assert_eq!(map.insert_after(&3, 3, 300), Ok(Some(30)));
+        assert_eq!(map.get_index(4), Some((&3, &300)));
+
+        // Missing anchor doesn't modify the map.
+        assert_eq!(map.insert_after(&100, 3, 3), Err((3, 3)));
+        assert_eq!(map.get(&3), Some(&300));
+
+        assert_eq!(map.len(), 7);
+        assert_positions(&map);
+    }
+
+    #[test]
+    fn test_map_move_index() {
+        let mut map = OmniMap::new();
+
+        for i in 0..100 {
+            map.insert(i, i);
+        }
+
+        // Small moves use the hashes, large moves use linear search.
+        map.move_index(10, 12);
+        map.move_index(12, 10);
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            (0..100).collect::<Vec<u32>>()
+        );
+
+        map.move_index(0, 99);
+        assert_eq!(map.get_index(99), Some((&0, &0)));
+        assert_eq!(map.get_index(0), Some((&1, &1)));
+        assert_positions(&map);
+
+        map.move_index(99, 0);
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            (0..100).collect::<Vec<u32>>()
+        );
+        assert_positions(&map);
+
+        map.move_index(5, 2);
+        assert_eq!(
+            map.iter_keys().take(7).copied().collect::<Vec<u32>>(),
+            vec![0, 1, 5, 2, 3, 4, 6]
+        );
+
+        // Same position.
+        map.move_index(50, 50);
+        assert_eq!(map.get_index(50), Some((&50, &50)));
+
+        assert_positions(&map);
+
+        // No stale slots are left in the index.
+        assert_eq!(map.debug_occupied(), map.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds.")]
+    fn test_map_move_index_out_of_bounds() {
+        let mut map = OmniMap::new();
+        map.insert(1, 1);
+        map.move_index(0, 1);
+    }
+
+    #[test]
+    fn test_map_swap_indices() {
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        map.swap_indices(0, 9);
+        map.swap_indices(3, 4);
+        map.swap_indices(5, 5);
+
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![9, 1, 2, 4, 3, 5, 6, 7, 8, 0]
+        );
+        assert_positions(&map);
+
+        for i in 0..10 {
+            assert_eq!(map.get(&i), Some(&i));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds.")]
+    fn test_map_swap_indices_out_of_bounds() {
+        let mut map = OmniMap::new();
+        map.insert(1, 1);
+        map.swap_indices(0, 1);
+    }
+
+    #[test]
+    fn test_map_reverse() {
+        let mut map = OmniMap::new();
+
+        // Empty and unallocated.
+        map.reverse();
+
+        map.insert(0, 0);
+        map.reverse();
+        assert_eq!(map.get_index(0), Some((&0, &0)));
+
+        for i in 1..100 {
+            map.insert(i, i);
+        }
+
+        // After a removal.
+        map.shift_remove(&50);
+
+        map.reverse();
+
+        let mut expected: Vec<u32> = (0..100).rev().collect();
+        expected.retain(|&i| i != 50);
+
+        assert_eq!(map.iter_keys().copied().collect::<Vec<u32>>(), expected);
+        assert_positions(&map);
+
+        for i in 0..100 {
+            let expected = if i == 50 { None } else { Some(&i) };
+            assert_eq!(map.get(&i), expected);
+        }
+    }
+
+    #[test]
+    fn test_map_sort_keys() {
+        let mut map = OmniMap::with_hasher(CountingState::default());
+
+        for i in (0..100).rev() {
+            map.insert(i, i * 2);
+        }
+
+        // After a removal.
+        map.shift_remove(&50);
+
+        let built = *map.hasher().built.borrow();
+
+        map.sort_keys();
+
+        // No key is hashed again.
+        assert_eq!(*map.hasher().built.borrow(), built);
+
+        let mut expected: Vec<u32> = (0..100).collect();
+        expected.retain(|&i| i != 50);
+
+        assert_eq!(map.iter_keys().copied().collect::<Vec<u32>>(), expected);
+        assert_eq!(map.debug_occupied(), map.len());
+        assert_positions(&map);
+
+        for i in expected {
+            assert_eq!(map.get(&i), Some(&(i * 2)));
+        }
+    }
+
+    #[test]
+    fn test_map_sort_by() {
+        let mut map = OmniMap::new();
+
+        for i in 0..20 {
+            map.insert(i, i % 3);
+        }
+
+        // Stable: equal values keep the order of their keys.
+        map.sort_by(|_, v1, _, v2| v1.cmp(v2));
+
+        let expected: Vec<u32> = (0..20)
+            .filter(|i| i % 3 == 0)
+            .chain((0..20).filter(|i| i % 3 == 1))
+            .chain((0..20).filter(|i| i % 3 == 2))
+            .collect();
+
+        assert_eq!(map.iter_keys().copied().collect::<Vec<u32>>(), expected);
+        assert_positions(&map);
+    }
+
+    #[test]
+    fn test_map_sort_unstable_by() {
+        let mut map = OmniMap::new();
+
+        for i in 0..100 {
+            map.insert(i, i);
+        }
+
+        map.sort_unstable_by(|k1, _, k2, _| k2.cmp(k1));
+
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            (0..100).rev().collect::<Vec<u32>>()
+        );
+        assert_positions(&map);
+    }
+
+    #[test]
+    fn test_map_sort_by_cached_key() {
+        let mut map = OmniMap::new();
+
+        for i in 0..100 {
+            map.insert(i, 100 - i);
+        }
+
+        let mut calls = 0;
+        map.sort_by_cached_key(|_, v| {
+            calls += 1;
+            *v
+        });
+
+        // Called once per entry.
+        assert_eq!(calls, 100);
+
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            (0..100).rev().collect::<Vec<u32>>()
+        );
+        assert_positions(&map);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_map_sort_panic() {
+        let mut map = OmniMap::new();
+
+        for i in 0..100 {
+            map.insert(i, i);
+        }
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut calls = 0;
+            map.sort_by(|k1, _, k2, _| {
+                calls += 1;
+                if calls == 50 {
+                    panic!("Comparator panic");
+                }
+                k2.cmp(k1)
+            });
+        }));
+
+        assert!(result.is_err());
+
+        // The map must remain consistent.
+        assert_eq!(map.len(), 100);
+        assert_positions(&map);
+
+        for i in 0..100 {
+            assert_eq!(map.get(&i), Some(&i));
+        }
+    }
+
+    #[test]
+    fn test_map_retain() {
+        // The identity hasher places each key at the slot of its value.
+        let mut map = identity_map();
+
+        // Empty and unallocated.
+        map.retain(|_, _| false);
+        assert!(map.is_empty());
+
+        for i in 0..100 {
+            map.insert(i, i);
+        }
+
+        // After a removal.
+        map.shift_remove(&0);
+
+        map.retain(|key, _| key % 3 != 0);
+
+        let expected: Vec<u32> = (0..100).filter(|i| i % 3 != 0).collect();
+
+        assert_eq!(map.len(), expected.len());
+        assert_eq!(map.iter_keys().copied().collect::<Vec<u32>>(), expected);
+        assert_eq!(
+            slot_keys(&map),
+            (0..map.debug_allocated_cap() as u32)
+                .map(|i| expected.contains(&i).then_some(i))
+                .collect::<Vec<_>>()
+        );
+        assert_positions(&map);
+
+        for i in 0..100 {
+            let expected = if i % 3 != 0 { Some(&i) } else { None };
+            assert_eq!(map.get(&i), expected);
+        }
+
+        // Retain all.
+        map.retain(|_, _| true);
+        assert_eq!(map.len(), expected.len());
+
+        // Remove all.
+        map.retain(|_, _| false);
+        assert!(map.is_empty());
+        assert_eq!(map.get(&1), None);
+
+        // Still usable.
+        map.insert(1, 1);
+        assert_eq!(map.get(&1), Some(&1));
+    }
+
+    #[test]
+    fn test_map_retain_mut() {
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        map.retain_mut(|key, value| {
+            *value *= 10;
+            key % 2 == 0
+        });
+
+        assert_eq!(
+            map.iter()
+                .map(|(k, v)| (*k, *v))
+                .collect::<Vec<(u32, u32)>>(),
+            vec![(0, 0), (2, 20), (4, 40), (6, 60), (8, 80)]
+        );
+        assert_positions(&map);
+    }
+
+    #[test]
+    fn test_map_retain_drop() {
+        let count = Rc::new(RefCell::new(0));
+
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(
+                i,
+                DropCounter {
+                    count: count.clone(),
+                },
+            );
+        }
+
+        map.retain(|key, _| *key < 5);
+
+        // Each removed value is dropped exactly once.
+        assert_eq!(*count.borrow(), 5);
+
+        drop(map);
+        assert_eq!(*count.borrow(), 10);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_map_retain_panic() {
+        let count = Rc::new(RefCell::new(0));
+
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(
+                i,
+                DropCounter {
+                    count: count.clone(),
+                },
+            );
+        }
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            map.retain(|key, _| {
+                if *key == 6 {
+                    panic!("Predicate panic");
+                }
+                key % 2 == 0
+            });
+        }));
+
+        assert!(result.is_err());
+
+        // Removed before the panic: 1, 3, 5. Unprocessed entries are retained.
+        assert_eq!(*count.borrow(), 3);
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![0, 2, 4, 6, 7, 8, 9]
+        );
+
+        for i in 0..map.len() {
+            let (key, _) = map.get_index(i).unwrap();
+            assert_eq!(map.get_index_of(key), Some(i));
+        }
+
+        drop(map);
+        assert_eq!(*count.borrow(), 10);
+    }
+
+    #[test]
+    fn test_map_drain() {
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        let drained: Vec<(u32, u32)> = map.drain(3..6).collect();
+        assert_eq!(drained, vec![(3, 3), (4, 4), (5, 5)]);
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![0, 1, 2, 6, 7, 8, 9]
+        );
+        assert_positions(&map);
+
+        for i in 3..6 {
+            assert_eq!(map.get(&i), None);
+        }
+
+        // Prefix.
+        assert_eq!(map.drain(..2).count(), 2);
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![2, 6, 7, 8, 9]
+        );
+        assert_positions(&map);
+
+        // Suffix.
+        assert_eq!(map.drain(3..).count(), 2);
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![2, 6, 7]
+        );
+        assert_positions(&map);
+
+        // Empty range.
+        assert_eq!(map.drain(1..1).count(), 0);
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![2, 6, 7]
+        );
+        assert_positions(&map);
+
+        // Full range.
+        assert_eq!(map.drain(..).count(), 3);
+        assert!(map.is_empty());
+
+        // The map remains usable.
+        map.insert(1, 1);
+        assert_eq!(map.get(&1), Some(&1));
+        assert_positions(&map);
+    }
+
+    #[test]
+    fn test_map_drain_collisions() {
+        let mut map: OmniMap<u32, u32, BuildHasherDefault<CollidingHasher>> = OmniMap::default();
+
+        for i in 0..20 {
+            map.insert(i, i);
+        }
+
+        // All entries share a single probe sequence, so unindexing the drained range and the tail
+        // shifts the slots of the entries before the range.
+        assert_eq!(map.drain(5..12).count(), 7);
+        assert_eq!(map.len(), 13);
+        assert_eq!(map.debug_occupied(), map.len());
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            (0..5).chain(12..20).collect::<Vec<u32>>()
+        );
+        assert_positions(&map);
+
+        for i in 5..12 {
+            assert_eq!(map.get(&i), None);
+        }
+    }
+
+    #[test]
+    fn test_map_drain_double_ended() {
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        let mut drain = map.drain(2..8);
+        assert_eq!(drain.len(), 6);
+        assert_eq!(drain.next(), Some((2, 2)));
+        assert_eq!(drain.next_back(), Some((7, 7)));
+        assert_eq!(drain.len(), 4);
+        assert_eq!(
+            drain.by_ref().rev().collect::<Vec<_>>(),
+            vec![(6, 6), (5, 5), (4, 4), (3, 3)]
+        );
+        assert_eq!(drain.next(), None);
+        // Fused.
+        assert_eq!(drain.next_back(), None);
+        assert_eq!(drain.next(), None);
+        drop(drain);
+
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![0, 1, 8, 9]
+        );
+        assert_positions(&map);
+    }
+
+    #[test]
+    fn test_map_drain_unallocated() {
+        let mut map: OmniMap<u32, u32> = OmniMap::new();
+        assert_eq!(map.drain(..).count(), 0);
+        assert_eq!(map.capacity(), 0);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_map_drain_out_of_bounds() {
+        let mut map = OmniMap::new();
+
+        for i in 0..5 {
+            map.insert(i, i);
+        }
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            map.drain(2..6);
+        }));
+        assert!(result.is_err());
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            map.drain(3..2);
+        }));
+        assert!(result.is_err());
+
+        // The map is untouched.
+        assert_eq!(map.len(), 5);
+        assert_positions(&map);
+    }
+
+    #[test]
+    fn test_map_drain_drop() {
+        let count = Rc::new(RefCell::new(0));
+
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(
+                i,
+                DropCounter {
+                    count: count.clone(),
+                },
+            );
+        }
+
+        let mut drain = map.drain(2..8);
+        drop(drain.next());
+        assert_eq!(*count.borrow(), 1);
+
+        // The remaining entries in the range are dropped with the iterator.
+        drop(drain);
+        assert_eq!(*count.borrow(), 6);
+
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![0, 1, 8, 9]
+        );
+
+        for i in 0..map.len() {
+            let (key, _) = map.get_index(i).unwrap();
+            assert_eq!(map.get_index_of(key), Some(i));
+        }
+
+        drop(map);
+        assert_eq!(*count.borrow(), 10);
+    }
+
+    #[test]
+    fn test_map_drain_forget() {
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        let mut drain = map.drain(4..7);
+        assert_eq!(drain.next(), Some((4, 4)));
+        mem::forget(drain);
+
+        // Only the entries before the range remain.
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![0, 1, 2, 3]
+        );
+        assert_positions(&map);
+
+        for i in 4..10 {
+            assert_eq!(map.get(&i), None);
+        }
+
+        for i in 4..10 {
+            map.insert(i, i * 10);
+        }
+        assert_eq!(map.len(), 10);
+        assert_eq!(map.get(&9), Some(&90));
+        assert_positions(&map);
+    }
+
+    #[test]
+    fn test_map_extract_if() {
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        let extracted: Vec<(u32, u32)> = map
+            .extract_if(|key, value| {
+                *value *= 10;
+                key % 3 == 0
+            })
+            .collect();
+
+        assert_eq!(extracted, vec![(0, 0), (3, 30), (6, 60), (9, 90)]);
+        assert_eq!(
+            map.iter()
+                .map(|(k, v)| (*k, *v))
+                .collect::<Vec<(u32, u32)>>(),
+            vec![(1, 10), (2, 20), (4, 40), (5, 50), (7, 70), (8, 80)]
+        );
+        assert_positions(&map);
+
+        let mut iter = map.extract_if(|_, _| false);
+        assert_eq!(iter.next(), None);
+        // Fused.
+        assert_eq!(iter.next(), None);
+        drop(iter);
+        assert_eq!(map.len(), 6);
+        assert_positions(&map);
+
+        assert_eq!(map.extract_if(|_, _| true).count(), 6);
+        assert!(map.is_empty());
+
+        map.insert(1, 1);
+        assert_eq!(map.get(&1), Some(&1));
+    }
+
+    #[test]
+    fn test_map_extract_if_early_drop() {
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        let mut iter = map.extract_if(|key, _| key % 2 == 1);
+        assert_eq!(iter.next(), Some((1, 1)));
+        assert_eq!(iter.next(), Some((3, 3)));
+        drop(iter);
+
+        // The entries that have not been visited are retained.
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![0, 2, 4, 5, 6, 7, 8, 9]
+        );
+        assert_positions(&map);
+    }
+
+    #[test]
+    fn test_map_extract_if_forget() {
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        let mut iter = map.extract_if(|key, _| key % 2 == 1);
+        assert_eq!(iter.next(), Some((1, 1)));
+        mem::forget(iter);
+
+        // The map is left empty but usable.
+        assert!(map.is_empty());
+        for i in 0..10 {
+            assert_eq!(map.get(&i), None);
+        }
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+        assert_eq!(map.len(), 10);
+        assert_positions(&map);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_map_extract_if_panic() {
+        let count = Rc::new(RefCell::new(0));
+
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(
+                i,
+                DropCounter {
+                    count: count.clone(),
+                },
+            );
+        }
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let iter = map.extract_if(|key, _| {
+                if *key == 6 {
+                    panic!("Predicate panic");
+                }
+                key % 2 == 1
+            });
+            iter.for_each(drop);
+        }));
+
+        assert!(result.is_err());
+
+        // Extracted before the panic: 1, 3, 5. Unprocessed entries are retained.
+        assert_eq!(*count.borrow(), 3);
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![0, 2, 4, 6, 7, 8, 9]
+        );
+
+        for i in 0..map.len() {
+            let (key, _) = map.get_index(i).unwrap();
+            assert_eq!(map.get_index_of(key), Some(i));
+        }
+
+        drop(map);
+        assert_eq!(*count.borrow(), 10);
+    }
+
+    #[test]
+    fn test_map_truncate() {
+        // The identity hasher places each key at the slot of its value.
+        let mut map = identity_map();
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        let cap = map.capacity();
+
+        map.truncate(10);
+        assert_eq!(map.len(), 10);
+
+        map.truncate(6);
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+        assert_eq!(map.capacity(), cap);
+        assert_eq!(
+            slot_keys(&map),
+            (0..map.debug_allocated_cap() as u32)
+                .map(|i| (i < 6).then_some(i))
+                .collect::<Vec<_>>()
+        );
+        assert_positions(&map);
+
+        for i in 6..10 {
+            assert_eq!(map.get(&i), None);
+        }
+
+        // The removed keys can be inserted again.
+        for i in 6..12 {
+            map.insert(i, i);
+        }
+        assert_eq!(map.len(), 12);
+        assert_positions(&map);
+
+        map.truncate(0);
+        assert!(map.is_empty());
+        assert_eq!(map.get(&0), None);
+    }
+
+    #[test]
+    fn test_map_truncate_drop() {
+        let count = Rc::new(RefCell::new(0));
+
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(
+                i,
+                DropCounter {
+                    count: count.clone(),
+                },
+            );
+        }
+
+        map.truncate(3);
+        assert_eq!(*count.borrow(), 7);
+        assert_eq!(map.len(), 3);
+
+        drop(map);
+        assert_eq!(*count.borrow(), 10);
+    }
+
+    #[test]
+    fn test_map_split_off() {
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i * 10);
+        }
+
+        let mut other = map.split_off(6);
+
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<u32>>(),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            other
+                .iter()
+                .map(|(k, v)| (*k, *v))
+                .collect::<Vec<(u32, u32)>>(),
+            vec![(6, 60), (7, 70), (8, 80), (9, 90)]
+        );
+        assert_positions(&map);
+        assert_positions(&other);
+
+        for i in 6..10 {
+            assert_eq!(map.get(&i), None);
+            assert_eq!(other.get(&i), Some(&(i * 10)));
+        }
+
+        // Both maps remain usable.
+        other.insert(0, 0);
+        map.insert(6, 6);
+        assert_eq!(other.get_index_of(&0), Some(4));
+        assert_eq!(map.get_index_of(&6), Some(6));
+
+        // Splitting at the length returns an empty map.
+        let empty = map.split_off(map.len());
+        assert!(empty.is_empty());
+        assert_eq!(map.len(), 7);
+
+        // Splitting at zero moves all the entries.
+        let all = map.split_off(0);
+        assert!(map.is_empty());
+        assert_eq!(all.len(), 7);
+        assert_positions(&all);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_map_split_off_out_of_bounds() {
+        let mut map = OmniMap::new();
+        map.insert(1, 1);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _ = map.split_off(2);
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_map_split_off_drop() {
+        let count = Rc::new(RefCell::new(0));
+
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(
+                i,
+                DropCounter {
+                    count: count.clone(),
+                },
+            );
+        }
+
+        let other = map.split_off(4);
+        assert_eq!(*count.borrow(), 0);
+
+        drop(other);
+        assert_eq!(*count.borrow(), 6);
+
+        drop(map);
+        assert_eq!(*count.borrow(), 10);
+    }
+
+    #[test]
+    fn test_map_append() {
+        let mut map = OmniMap::new();
+
+        for i in 0..5 {
+            map.insert(i, i);
+        }
+
+        let mut other = OmniMap::new();
+
+        for i in 3..8 {
+            other.insert(i, i * 10);
+        }
+
+        map.append(&mut other);
+
+        assert!(other.is_empty());
+        assert_eq!(
+            map.iter()
+                .map(|(k, v)| (*k, *v))
+                .collect::<Vec<(u32, u32)>>(),
+            vec![
+                (0, 0),
+                (1, 1),
+                (2, 2),
+                (3, 30),
+                (4, 40),
+                (5, 50),
+                (6, 60),
+                (7, 70)
+            ]
+        );
+        assert_positions(&map);
+
+        // The emptied map remains usable.
+        other.insert(1, 1);
+        assert_eq!(other.get(&1), Some(&1));
+
+        // Appending an empty map is no-op.
+        let mut empty = OmniMap::new();
+        map.append(&mut empty);
+        assert_eq!(map.len(), 8);
+    }
+
+    #[test]
+    fn test_map_from_iter() {
+        let map: OmniMap<u32, u32> = (0..100).map(|i| (i, i * 10)).collect();
+
+        // Reserved in advance from the exact size hint.
+        assert_eq!(map.len(), 100);
+        assert_eq!(map.capacity(), 100);
+        assert_positions(&map);
+
+        for i in 0..100 {
+            assert_eq!(map.get(&i), Some(&(i * 10)));
+        }
+
+        // The last value wins, the first position is kept.
+        let map: OmniMap<u32, u32> = vec![(1, 1), (2, 2), (1, 10), (3, 3), (2, 20)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            map.iter()
+                .map(|(k, v)| (*k, *v))
+                .collect::<Vec<(u32, u32)>>(),
+            vec![(1, 10), (2, 20), (3, 3)]
+        );
+        assert_positions(&map);
+
+        let empty: OmniMap<u32, u32> = core::iter::empty().collect();
+        assert!(empty.is_empty());
+        assert_eq!(empty.capacity(), 0);
+    }
+
+    #[test]
+    fn test_map_from_array() {
+        let map = OmniMap::from([(1, "a"), (2, "b"), (1, "c")]);
+
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&1, &"c"), (&2, &"b")]);
+    }
+
+    #[test]
+    fn test_map_extend() {
+        let alloc = CountingAlloc::default();
+
+        let mut map = OmniMap::with_hasher_in(RandomState::with_seed(0), alloc.clone());
+
+        map.extend((0..50).map(|i| (i, i)));
+
+        // A single allocation of the entries and the index.
+        assert_eq!(alloc.total.get(), 2);
+        assert_eq!(map.capacity(), 50);
+
+        map.extend((40..60).map(|i| (i, i * 10)));
+
+        assert_eq!(map.len(), 60);
+        for i in 0..40 {
+            assert_eq!(map.get(&i), Some(&i));
+        }
+        for i in 40..60 {
+            assert_eq!(map.get(&i), Some(&(i * 10)));
+        }
+        assert_eq!(map.get_index_of(&45), Some(45));
+        assert_eq!(map.get_index_of(&59), Some(59));
+
+        // Free capacity is used without reallocation.
+        let mut map = OmniMap::with_capacity(20);
+        let cap = map.capacity();
+        map.extend((0..10).map(|i| (i, i)));
+        assert_eq!(map.capacity(), cap);
+    }
+
+    #[test]
+    fn test_map_extend_refs() {
+        let source = [(1, 10), (2, 20), (3, 30)];
+
+        let mut map = OmniMap::new();
+        map.insert(2, 0);
+        map.extend(source.iter().map(|(k, v)| (k, v)));
+
+        assert_eq!(
+            map.iter()
+                .map(|(k, v)| (*k, *v))
+                .collect::<Vec<(u32, u32)>>(),
+            vec![(2, 20), (1, 10), (3, 30)]
+        );
+
+        let mut other = OmniMap::new();
+        other.extend(&map);
+        assert_eq!(other, map);
+    }
+
+    #[test]
+    fn test_map_as_slice() {
+        let mut map: OmniMap<u32, u32> = OmniMap::new();
+
+        // Unallocated map.
+        assert!(map.as_slice().is_empty());
+        assert!(map.as_mut_slice().is_empty());
+        assert!(map[..].is_empty());
+        assert_eq!(map.get_range(0..0).map(|s| s.len()), Some(0));
+        assert!(map.get_range(0..1).is_none());
+
+        for i in 0..10 {
+            map.insert(i, i * 10);
+        }
+
+        let slice = map.as_slice();
+        assert_eq!(slice.len(), 10);
+        assert_eq!(slice.first(), Some((&0, &0)));
+        assert_eq!(slice.last(), Some((&9, &90)));
+        assert_eq!(slice.get_index(3), Some((&3, &30)));
+        assert_eq!(slice.get_index(10), None);
+        assert_eq!(slice[4], 40);
+        assert_eq!(
+            slice
+                .iter()
+                .map(|(k, v)| (*k, *v))
+                .collect::<Vec<(u32, u32)>>(),
+            map.iter()
+                .map(|(k, v)| (*k, *v))
+                .collect::<Vec<(u32, u32)>>()
+        );
+    }
+
+    #[test]
+    fn test_map_slice_ranges() {
+        let mut map: OmniMap<u32, u32> = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i * 10);
+        }
+
+        assert_eq!(
+            map[2..5].keys().copied().collect::<Vec<u32>>(),
+            vec![2, 3, 4]
+        );
+        assert_eq!(
+            map[..3].keys().copied().collect::<Vec<u32>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(map[8..].keys().copied().collect::<Vec<u32>>(), vec![8, 9]);
+        assert_eq!(map[..=1].keys().copied().collect::<Vec<u32>>(), vec![0, 1]);
+        assert_eq!(
+            map[3..=4].values().copied().collect::<Vec<u32>>(),
+            vec![30, 40]
+        );
+        assert_eq!(map[..].len(), 10);
+
+        // Sub-slicing is relative to the slice.
+        let slice = &map[2..8];
+        assert_eq!(
+            slice[1..3].keys().copied().collect::<Vec<u32>>(),
+            vec![3, 4]
+        );
+        assert_eq!(slice.get_range(4..).map(|s| s.len()), Some(2));
+        assert!(slice.get_range(4..7).is_none());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = slice.get_range(3..2);
+        assert!(reversed.is_none());
+
+        let (head, tail) = slice.split_at(2);
+        assert_eq!(head.keys().copied().collect::<Vec<u32>>(), vec![2, 3]);
+        assert_eq!(tail.keys().copied().collect::<Vec<u32>>(), vec![4, 5, 6, 7]);
+
+        let (first, rest) = slice.split_first().unwrap();
+        assert_eq!(first, (&2, &20));
+        assert_eq!(rest.len(), 5);
+
+        let (last, rest) = slice.split_last().unwrap();
+        assert_eq!(last, (&7, &70));
+        assert_eq!(rest.len(), 5);
+
+        let empty = &map[5..5];
+        assert!(empty.split_first().is_none());
+        assert!(empty.split_last().is_none());
+        assert!(empty.first().is_none());
+        assert_eq!(empty, &map[0..0]);
+
+        assert_eq!(&map[1..3], &map[1..3]);
+        assert_ne!(&map[1..3], &map[2..4]);
+        assert_eq!(format!("{:?}", &map[1..3]), "{1: 10, 2: 20}");
+    }
+
+    #[test]
+    fn test_map_slice_mut() {
+        let mut map: OmniMap<u32, u32> = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        for value in map[5..].values_mut() {
+            *value *= 10;
+        }
+
+        map[..2][1] = 100;
+
+        {
+            let slice = map.get_range_mut(2..5).unwrap();
+
+            if let Some((_, value)) = slice.first_mut() {
+                *value = 200;
+            }
+            if let Some((_, value)) = slice.last_mut() {
+                *value = 400;
+            }
+
+            let (head, tail) = slice.split_at_mut(1);
+            *head.get_index_mut(0).unwrap().1 += 1;
+            for (_, value) in tail.iter_mut() {
+                *value += 1;
+            }
+        }
+
+        let (first, rest) = map.as_mut_slice().split_first_mut().unwrap();
+        *first.1 = 1000;
+        let (last, _) = rest.split_last_mut().unwrap();
+        *last.1 += 1;
+
+        assert_eq!(
+            map.iter_values().copied().collect::<Vec<u32>>(),
+            vec![1000, 100, 201, 4, 401, 50, 60, 70, 80, 91]
+        );
+
+        // The keys and the index are not affected.
+        assert_positions(&map);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_map_slice_out_of_bounds() {
+        let mut map: OmniMap<u32, u32> = OmniMap::new();
+
+        for i in 0..5 {
+            map.insert(i, i);
+        }
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _ = &map[3..6];
+        }));
+        assert!(result.is_err());
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _ = map[1..3][2];
+        }));
+        assert!(result.is_err());
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _ = map.as_slice().split_at(6);
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_map_binary_search() {
+        let mut map: OmniMap<u32, u32> = OmniMap::new();
+
+        assert_eq!(map.binary_search_keys(&1), Err(0));
+        assert_eq!(map.partition_point(|_, _| true), 0);
+
+        for i in (0..20).step_by(2) {
+            map.insert(i, i * 10);
+        }
+
+        for i in 0..10 {
+            assert_eq!(map.binary_search_keys(&(i * 2)), Ok(i as usize));
+            assert_eq!(map.binary_search_keys(&(i * 2 + 1)), Err(i as usize + 1));
+        }
+
+        assert_eq!(map.binary_search_by(|_, v| v.cmp(&60)), Ok(3));
+        assert_eq!(map.binary_search_by_key(&70, |_, v| *v), Err(4));
+        assert_eq!(map.partition_point(|k, _| *k < 7), 4);
+
+        // The insertion position keeps the order.
+        for key in [7, 21, 0] {
+            if let Err(index) = map.binary_search_keys(&key) {
+                map.shift_insert(index, key, key * 10);
+            }
+        }
+
+        let keys: Vec<u32> = map.iter_keys().copied().collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+        assert_eq!(map.len(), 12);
+        assert_positions(&map);
+    }
+
+    #[test]
+    fn test_map_slice_binary_search() {
+        let mut map: OmniMap<u32, u32> = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        // The indices are relative to the slice.
+        let slice = &map[4..8];
+        assert_eq!(slice.binary_search_keys(&5), Ok(1));
+        assert_eq!(slice.binary_search_keys(&2), Err(0));
+        assert_eq!(slice.binary_search_keys(&9), Err(4));
+        assert_eq!(slice.binary_search_by(|k, _| k.cmp(&7)), Ok(3));
+        assert_eq!(slice.binary_search_by_key(&12, |_, v| v * 2), Ok(2));
+        assert_eq!(slice.partition_point(|k, _| *k < 6), 2);
+    }
+
+    #[test]
+    fn test_map_named_iterators() {
+        let mut map: OmniMap<u32, u32> = OmniMap::new();
+
+        assert_eq!(map.iter().len(), 0);
+        assert_eq!(map.iter_keys().next_back(), None);
+        assert_eq!(map.values_mut().next(), None);
+
+        for i in 0..10 {
+            map.insert(i, i * 10);
+        }
+
+        let mut iter = map.iter();
+        assert_eq!(iter.len(), 10);
+        assert_eq!(iter.next(), Some((&0, &0)));
+        assert_eq!(iter.next_back(), Some((&9, &90)));
+        assert_eq!(iter.nth(2), Some((&3, &30)));
+        assert_eq!(iter.nth_back(1), Some((&7, &70)));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.clone().count(), 3);
+        assert_eq!(iter.last(), Some((&6, &60)));
+
+        let mut keys = map.iter_keys();
+        assert_eq!(keys.nth(8), Some(&8));
+        assert_eq!(keys.next(), Some(&9));
+        assert_eq!(keys.next(), None);
+        // Fused.
+        assert_eq!(keys.next(), None);
+
+        assert_eq!(
+            map.iter_values().rev().copied().collect::<Vec<u32>>(),
+            (0..10).rev().map(|i| i * 10).collect::<Vec<u32>>()
+        );
+        assert_eq!(map.iter_values().last(), Some(&90));
+
+        for value in map.values_mut().rev().take(2) {
+            *value += 1;
+        }
+        for (_, value) in map.iter_mut().skip(1).step_by(4) {
+            *value += 2;
+        }
+        assert_eq!(
+            map.iter_values().copied().collect::<Vec<u32>>(),
+            vec![0, 12, 20, 30, 40, 52, 60, 70, 81, 93]
+        );
+
+        let mut keys = map.iter_keys();
+        keys.nth(7);
+        assert_eq!(format!("{:?}", keys), "[8, 9]");
+        assert_eq!(format!("{:?}", map.as_slice()[..1].iter()), "[(0, 0)]");
+    }
+
+    #[test]
+    fn test_map_into_keys_values() {
+        let mut map: OmniMap<u32, u32> = OmniMap::new();
+
+        for i in 0..6 {
+            map.insert(i, i * 10);
+        }
+
+        let mut keys = map.clone().into_keys();
+        assert_eq!(keys.len(), 6);
+        assert_eq!(keys.next_back(), Some(5));
+        assert_eq!(keys.nth(1), Some(1));
+        assert_eq!(keys.collect::<Vec<u32>>(), vec![2, 3, 4]);
+
+        let mut values = map.into_values();
+        assert_eq!(values.nth_back(1), Some(40));
+        assert_eq!(values.last(), Some(30));
+    }
+
+    #[test]
+    fn test_map_into_iter_double_ended() {
+        let count = Rc::new(RefCell::new(0));
+
+        let mut map = OmniMap::new();
+
+        for i in 0..10 {
+            map.insert(
+                i,
+                DropCounter {
+                    count: count.clone(),
+                },
+            );
+        }
+
+        let mut iter = map.into_iter();
+
+        assert_eq!(iter.next_back().map(|(k, _)| k), Some(9));
+        assert_eq!(*count.borrow(), 1);
+
+        // The skipped entries are dropped.
+        assert_eq!(iter.nth(2).map(|(k, _)| k), Some(2));
+        assert_eq!(*count.borrow(), 4);
+
+        assert_eq!(iter.nth_back(1).map(|(k, _)| k), Some(7));
+        assert_eq!(*count.borrow(), 6);
+        assert_eq!(iter.len(), 4);
+
+        assert_eq!(iter.nth(10).map(|(k, _)| k), None);
+        assert_eq!(*count.borrow(), 10);
+        assert_eq!(iter.next().map(|(k, _)| k), None);
+    }
+
+    #[test]
+    fn test_map_into_iter_last() {
+        let count = Rc::new(RefCell::new(0));
+
+        let mut map = OmniMap::new();
+
+        for i in 0..5 {
+            map.insert(
+                i,
+                DropCounter {
+                    count: count.clone(),
+                },
+            );
+        }
+
+        let last = map.into_iter().last();
+        assert_eq!(last.as_ref().map(|(k, _)| *k), Some(4));
+        assert_eq!(*count.borrow(), 4);
+
+        drop(last);
+        assert_eq!(*count.borrow(), 5);
+    }
+
+    #[test]
+    fn test_map_get_disjoint_mut() {
+        let mut map: OmniMap<u32, u32> = OmniMap::new();
+
+        assert!(map.get_disjoint_mut([&1]).is_none());
+        assert!(map.get_disjoint_mut::<u32, 0>([]).is_some());
+
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        let [a, b, c] = map.get_disjoint_mut([&7, &2, &9]).unwrap();
+        assert_eq!((*a, *b, *c), (7, 2, 9));
+        mem::swap(a, b);
+        *c = 90;
+
+        assert_eq!(map.get(&7), Some(&2));
+        assert_eq!(map.get(&2), Some(&7));
+        assert_eq!(map.get(&9), Some(&90));
+
+        assert!(map.get_disjoint_mut([&1, &2, &1]).is_none());
+        assert!(map.get_disjoint_mut([&1, &10]).is_none());
+        assert_positions(&map);
+    }
+
+    #[test]
+    fn test_map_get_disjoint_mut_borrowed() {
+        let mut map: OmniMap<String, u32> = OmniMap::new();
+
+        map.insert("a".to_string(), 1);
+        map.insert("b".to_string(), 2);
+
+        let [a, b] = map.get_disjoint_mut(["a", "b"]).unwrap();
+        *a += 10;
+        *b += 20;
+
+        assert_eq!(map.get("a"), Some(&11));
+        assert_eq!(map.get("b"), Some(&22));
+    }
+
+    #[test]
+    fn test_map_get_disjoint_indices_mut() {
+        let mut map: OmniMap<u32, u32> = OmniMap::new();
+
+        assert!(map.get_disjoint_indices_mut([0]).is_none());
+
+        for i in 0..5 {
+            map.insert(i, i * 10);
+        }
+
+        let [(k0, v0), (k4, v4)] = map.get_disjoint_indices_mut([4, 0]).unwrap();
+        assert_eq!((*k0, *k4), (4, 0));
+        mem::swap(v0, v4);
+
+        assert_eq!(map.get(&0), Some(&40));
+        assert_eq!(map.get(&4), Some(&0));
+
+        assert!(map.get_disjoint_indices_mut([1, 2, 1]).is_none());
+        assert!(map.get_disjoint_indices_mut([1, 5]).is_none());
+        assert_eq!(map.get_disjoint_indices_mut([]), Some([]));
+    }
+
+    #[test]
+    fn test_map_eq_ordered() {
+        let mut a = OmniMap::with_hasher(RandomState::with_seed(1));
+        let mut b = OmniMap::with_hasher(RandomState::with_seed(1));
+
+        for i in 0..100 {
+            a.insert(i, i * 2);
+            b.insert(99 - i, (99 - i) * 2);
+        }
+
+        // Same entries in a different order.
+        assert_eq!(a, b);
+        assert!(!a.eq_ordered(&b));
+
+        b.reverse();
+        assert!(a.eq_ordered(&b));
+        assert!(b.eq_ordered(&a));
+
+        // Different values at the same position.
+        b.insert(50, 0);
+        assert_ne!(a, b);
+        assert!(!a.eq_ordered(&b));
+
+        // Different lengths.
+        b.insert(50, 100);
+        b.pop();
+        assert!(!a.eq_ordered(&b));
+
+        // The hash builders don't have to be equal.
+        let mut c = OmniMap::with_hasher(RandomState::with_seed(2));
+        c.extend(a.iter().map(|(k, v)| (*k, *v)));
+        assert!(a.eq_ordered(&c));
+        c.swap_indices(0, 1);
+        assert!(!a.eq_ordered(&c));
+
+        let empty: OmniMap<u32, u32, RandomState> = OmniMap::with_hasher(RandomState::with_seed(1));
+        let other: OmniMap<u32, u32, RandomState> = OmniMap::with_hasher(RandomState::with_seed(2));
+        assert!(empty.eq_ordered(&other));
+
+        // Hash builders without `PartialEq`.
+        let mut d = OmniMap::with_hasher(std::collections::hash_map::RandomState::new());
+        let mut e = OmniMap::with_hasher(std::collections::hash_map::RandomState::new());
+        d.extend([(1, 1), (2, 2)]);
+        e.extend([(1, 1), (2, 2)]);
+        assert!(d.eq_ordered(&e));
+    }
+
+    #[test]
+    fn test_map_eq_ordered_compares_hashes() {
+        /// A key that counts how many times it is compared.
+        struct CountingKey(u32, Rc<Cell<usize>>);
+
+        impl Hash for CountingKey {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+
+        impl PartialEq for CountingKey {
+            fn eq(&self, other: &Self) -> bool {
+                self.1.set(self.1.get() + 1);
+                self.0 == other.0
+            }
+        }
+
+        impl Eq for CountingKey {}
+
+        let compared = Rc::new(Cell::new(0));
+        let build = |hash_builder, swap| {
+            let mut map = OmniMap::with_hasher(hash_builder);
+            for i in 0..100 {
+                map.insert(CountingKey(i, compared.clone()), i);
+            }
+            if swap {
+                map.swap_indices(0, 1);
+            }
+            map
+        };
+
+        let a = build(RandomState::with_seed(1), false);
+        let b = build(RandomState::with_seed(1), false);
+        let c = build(RandomState::with_seed(1), true);
+        let d = build(RandomState::with_seed(2), true);
+
+        // The builders are equal, so the different keys are rejected by their hashes.
+        compared.set(0);
+        assert!(!a.eq_ordered(&c));
+        assert_eq!(compared.get(), 0);
+
+        // The builders are different, so the keys must be compared.
+        assert!(!a.eq_ordered(&d));
+        assert_eq!(compared.get(), 1);
+
+        // Equal keys are always compared.
+        compared.set(0);
+        assert!(a.eq_ordered(&b));
+        assert_eq!(compared.get(), 100);
+    }
+
+    #[test]
+    fn test_ordered_map() {
+        use crate::ordered::OrderedMap;
+        use core::cmp::Ordering;
+        use std::collections::HashSet;
+
+        let a: OmniMap<u32, u32> = OmniMap::from([(1, 10), (2, 20)]);
+        let b: OmniMap<u32, u32> = OmniMap::from([(2, 20), (1, 10)]);
+        let c: OmniMap<u32, u32> = OmniMap::from([(1, 10), (2, 20), (3, 30)]);
+
+        let a = OrderedMap::new(a);
+        let b = OrderedMap::new(b);
+        let c = OrderedMap::from(c);
+
+        assert_ne!(a, b);
+        assert_eq!(a, a.clone());
+
+        // Lexicographic order of the entries, and a prefix is less.
+        assert_eq!(a.cmp(&b), Ordering::Less);
+        assert_eq!(a.cmp(&c), Ordering::Less);
+        assert_eq!(b.cmp(&c), Ordering::Greater);
+        assert_eq!(a.partial_cmp(&a.clone()), Some(Ordering::Equal));
+
+        // Equal maps with different hash builders have equal hashes.
+        let state = std::collections::hash_map::RandomState::new();
+        let copy = OrderedMap::new(OmniMap::from([(1, 10), (2, 20)]));
+        assert_eq!(state.hash_one(&a), state.hash_one(&copy));
+        assert_ne!(state.hash_one(&a), state.hash_one(&b));
+
+        let mut seen = HashSet::new();
+        assert!(seen.insert(a.clone()));
+        assert!(seen.insert(b.clone()));
+        assert!(!seen.insert(a.clone()));
+        assert_eq!(seen.len(), 2);
+
+        // The wrapper dereferences to the map.
+        let mut d = a.clone();
+        d.insert(3, 30);
+        assert_eq!(d.len(), 3);
+        assert_eq!(d, c);
+        assert_eq!(format!("{:?}", d), "{1: 10, 2: 20, 3: 30}");
+        assert_eq!(d.into_inner().get(&3), Some(&30));
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod set_tests {
+    use crate::set::OmniSet;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_set_insert_contains() {
+        let mut set = OmniSet::new();
+
+        assert!(set.is_empty());
+        assert_eq!(set.capacity(), 0);
+
+        for i in 0..10 {
+            assert!(set.insert(i));
+        }
+
+        assert!(!set.insert(5));
+        assert_eq!(set.insert_full(5), (5, false));
+        assert_eq!(set.insert_full(10), (10, true));
+        assert_eq!(set.len(), 11);
+
+        for i in 0..11 {
+            assert!(set.contains(&i));
+            assert_eq!(set.get(&i), Some(&i));
+            assert_eq!(set.get_index_of(&i), Some(i as usize));
+            assert_eq!(set.get_index(i as usize), Some(&i));
+            assert_eq!(set[i as usize], i);
+        }
+
+        assert!(!set.contains(&11));
+        assert_eq!(set.first(), Some(&0));
+        assert_eq!(set.last(), Some(&10));
+    }
+
+    #[test]
+    fn test_set_insert_keeps_first_value() {
+        #[derive(Debug)]
+        struct Tagged(u32, &'static str);
+
+        impl PartialEq for Tagged {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl Eq for Tagged {}
+
+        impl std::hash::Hash for Tagged {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+
+        let mut set = OmniSet::new();
+        set.insert(Tagged(1, "first"));
+        assert!(!set.insert(Tagged(1, "second")));
+        assert_eq!(set[0].1, "first");
+    }
+
+    #[test]
+    fn test_set_remove() {
+        let mut set = OmniSet::from([1, 2, 3, 4, 5]);
+
+        assert!(set.shift_remove(&2));
+        assert!(!set.shift_remove(&2));
+        assert_eq!(set.iter().copied().collect::<Vec<i32>>(), vec![1, 3, 4, 5]);
+
+        assert!(set.swap_remove(&1));
+        assert_eq!(set.iter().copied().collect::<Vec<i32>>(), vec![5, 3, 4]);
+
+        assert_eq!(set.pop(), Some(4));
+        assert_eq!(set.pop_front(), Some(5));
+        assert_eq!(set.iter().copied().collect::<Vec<i32>>(), vec![3]);
+        assert_eq!(set.get_index_of(&3), Some(0));
+
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set.pop(), None);
+    }
+
+    #[test]
+    fn test_set_reorder() {
+        let mut set = OmniSet::from([3, 1, 4, 1, 5, 9, 2, 6]);
+        assert_eq!(set.len(), 7);
+
+        set.sort();
+        assert_eq!(
+            set.iter().copied().collect::<Vec<i32>>(),
+            vec![1, 2, 3, 4, 5, 6, 9]
+        );
+
+        set.sort_by(|a, b| b.cmp(a));
+        assert_eq!(
+            set.iter().copied().collect::<Vec<i32>>(),
+            vec![9, 6, 5, 4, 3, 2, 1]
+        );
+
+        set.reverse();
+        set.move_index(0, 6);
+        set.swap_indices(0, 1);
+        assert_eq!(
+            set.iter().copied().collect::<Vec<i32>>(),
+            vec![3, 2, 4, 5, 6, 9, 1]
+        );
+
+        assert!(set.shift_insert(0, 7));
+        assert!(!set.shift_insert(0, 9));
+        assert_eq!(
+            set.iter().copied().collect::<Vec<i32>>(),
+            vec![9, 7, 3, 2, 4, 5, 6, 1]
+        );
+
+        set.retain(|v| v % 2 == 1);
+        assert_eq!(
+            set.iter().copied().collect::<Vec<i32>>(),
+            vec![9, 7, 3, 5, 1]
+        );
+
+        set.truncate(2);
+        assert_eq!(set.iter().copied().collect::<Vec<i32>>(), vec![9, 7]);
+
+        for (i, v) in set.iter().enumerate() {
+            assert_eq!(set.get_index_of(v), Some(i));
+        }
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a = OmniSet::from([5, 1, 3, 7]);
+        let b = OmniSet::from([6, 3, 5, 2]);
+
+        assert_eq!(
+            a.union(&b).copied().collect::<Vec<i32>>(),
+            vec![5, 1, 3, 7, 6, 2]
+        );
+        assert_eq!(
+            b.union(&a).copied().collect::<Vec<i32>>(),
+            vec![6, 3, 5, 2, 1, 7]
+        );
+        assert_eq!(
+            a.intersection(&b).copied().collect::<Vec<i32>>(),
+            vec![5, 3]
+        );
+        assert_eq!(
+            b.intersection(&a).copied().collect::<Vec<i32>>(),
+            vec![3, 5]
+        );
+        assert_eq!(a.difference(&b).copied().collect::<Vec<i32>>(), vec![1, 7]);
+        assert_eq!(b.difference(&a).copied().collect::<Vec<i32>>(), vec![6, 2]);
+        assert_eq!(
+            a.symmetric_difference(&b).copied().collect::<Vec<i32>>(),
+            vec![1, 7, 6, 2]
+        );
+
+        let empty = OmniSet::new();
+        assert_eq!(a.union(&empty).count(), 4);
+        assert_eq!(a.intersection(&empty).count(), 0);
+        assert_eq!(empty.difference(&a).count(), 0);
+
+        assert!(OmniSet::from([1, 7]).is_subset(&a));
+        assert!(!OmniSet::from([1, 2]).is_subset(&a));
+        assert!(empty.is_subset(&a));
+        assert!(a.is_superset(&OmniSet::from([3])));
+        assert!(a.is_disjoint(&OmniSet::from([2, 4, 6])));
+        assert!(!a.is_disjoint(&b));
+        assert!(a.is_disjoint(&empty));
+    }
+
+    #[test]
+    fn test_set_eq_clone_debug() {
+        let a = OmniSet::from([1, 2, 3]);
+        let b = OmniSet::from([3, 1, 2]);
+
+        // Equality doesn't depend on the order.
+        assert_eq!(a, b);
+        assert_ne!(a, OmniSet::from([1, 2]));
+
+        let c = a.clone();
+        assert_eq!(c.iter().copied().collect::<Vec<i32>>(), vec![1, 2, 3]);
+
+        assert_eq!(format!("{:?}", a), "{1, 2, 3}");
+    }
+
+    #[test]
+    fn test_set_iterators() {
+        let set: OmniSet<u32> = (0..10).chain(5..15).collect();
+        assert_eq!(set.len(), 15);
+
+        let mut iter = set.iter();
+        assert_eq!(iter.len(), 15);
+        assert_eq!(iter.next_back(), Some(&14));
+        assert_eq!(iter.nth(2), Some(&2));
+
+        let mut extended = OmniSet::from([20, 1]);
+        extended.extend(&[1, 2, 3]);
+        extended.extend(vec![3, 4]);
+        assert_eq!(
+            extended.iter().copied().collect::<Vec<u32>>(),
+            vec![20, 1, 2, 3, 4]
+        );
+
+        let mut owned = extended.into_iter();
+        assert_eq!(owned.len(), 5);
+        assert_eq!(owned.next_back(), Some(4));
+        assert_eq!(owned.collect::<Vec<u32>>(), vec![20, 1, 2, 3]);
+
+        let mut sum = 0;
+        for v in &set {
+            sum += v;
+        }
+        assert_eq!(sum, (0..15).sum::<u32>());
+    }
+
+    #[test]
+    fn test_set_drop() {
+        struct Counted(u32, Rc<RefCell<usize>>);
+
+        impl PartialEq for Counted {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl Eq for Counted {}
+
+        impl std::hash::Hash for Counted {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                *self.1.borrow_mut() += 1;
+            }
+        }
+
+        let count = Rc::new(RefCell::new(0));
+
+        let mut set = OmniSet::new();
+
+        for i in 0..10 {
+            set.insert(Counted(i, count.clone()));
+        }
+
+        // The rejected duplicate is dropped.
+        assert!(!set.insert(Counted(0, count.clone())));
+        assert_eq!(*count.borrow(), 1);
+
+        drop(set);
+        assert_eq!(*count.borrow(), 11);
+    }
+}
+
+#[cfg(all(test, feature = "std", feature = "serde"))]
+mod serde_tests {
+    use crate::{DuplicateKeys, OmniMap, OmniSet};
+
+    #[test]
+    fn test_map_serialize_order() {
+        let mut map = OmniMap::new();
+        map.insert("c", 1);
+        map.insert("a", 2);
+        map.insert("b", 3);
+        map.shift_remove(&"a");
+        map.insert("a", 4);
+
+        assert_eq!(
+            serde_json::to_string(&map).unwrap(),
+            r#"{"c":1,"b":3,"a":4}"#
+        );
+
+        // Slices are serialized as maps too.
+        assert_eq!(
+            serde_json::to_string(&map[1..]).unwrap(),
+            r#"{"b":3,"a":4}"#
+        );
+
+        let empty: OmniMap<u32, u32> = OmniMap::new();
+        assert_eq!(serde_json::to_string(&empty).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_map_deserialize_order() {
+        let json = r#"{"z":1,"y":2,"x":3,"w":4}"#;
+
+        let map: OmniMap<String, u32> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            map.iter_keys().map(String::as_str).collect::<Vec<&str>>(),
+            vec!["z", "y", "x", "w"]
+        );
+        for (i, key) in ["z", "y", "x", "w"].iter().enumerate() {
+            assert_eq!(map.get_index_of(*key), Some(i));
+        }
+
+        // Round trip.
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+    }
+
+    #[test]
+    fn test_map_deserialize_duplicate_keys() {
+        let json = r#"{"a":1,"b":2,"a":3}"#;
+
+        let map: OmniMap<String, u32> = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"a":3,"b":2}"#);
+
+        let mut de = serde_json::Deserializer::from_str(json);
+        let map: OmniMap<String, u32> =
+            OmniMap::deserialize_with_policy(&mut de, DuplicateKeys::FirstWins).unwrap();
+        assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"a":1,"b":2}"#);
+
+        let mut de = serde_json::Deserializer::from_str(json);
+        let map: OmniMap<String, u32> =
+            OmniMap::deserialize_with_policy(&mut de, DuplicateKeys::LastWins).unwrap();
+        assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"a":3,"b":2}"#);
+
+        let mut de = serde_json::Deserializer::from_str(json);
+        let error = OmniMap::<String, u32>::deserialize_with_policy(&mut de, DuplicateKeys::Error)
+            .unwrap_err();
+        assert!(error.to_string().starts_with("duplicate key at index 0"));
+
+        // No duplicates.
+        let mut de = serde_json::Deserializer::from_str(r#"{"a":1,"b":2}"#);
+        let map: OmniMap<String, u32> =
+            OmniMap::deserialize_with_policy(&mut de, DuplicateKeys::Error).unwrap();
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn test_set_serialize_deserialize() {
+        let set = OmniSet::from([3, 1, 2]);
+
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(json, "[3,1,2]");
+
+        let set: OmniSet<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(set.iter().copied().collect::<Vec<u32>>(), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn test_set_deserialize_duplicate_values() {
+        // The second field isn't part of the identity of the value.
+        #[derive(Debug, serde::Deserialize)]
+        struct Tagged(u32, u32);
+
+        impl PartialEq for Tagged {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl Eq for Tagged {}
+
+        impl std::hash::Hash for Tagged {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+
+        let json = "[[1,0],[2,0],[1,1]]";
+
+        let set: OmniSet<Tagged> = serde_json::from_str(json).unwrap();
+        assert_eq!(set.len(), 2);
+        assert_eq!(set[0].1, 1);
+        assert_eq!(set.get_index_of(&Tagged(1, 5)), Some(0));
+
+        let mut de = serde_json::Deserializer::from_str(json);
+        let set: OmniSet<Tagged> =
+            OmniSet::deserialize_with_policy(&mut de, DuplicateKeys::FirstWins).unwrap();
+        assert_eq!(set.len(), 2);
+        assert_eq!(set[0].1, 0);
+
+        let mut de = serde_json::Deserializer::from_str(json);
+        let error =
+            OmniSet::<Tagged>::deserialize_with_policy(&mut de, DuplicateKeys::Error).unwrap_err();
+        assert!(error.to_string().starts_with("duplicate value at index 0"));
+    }
+}
+
+#[cfg(all(test, feature = "std", feature = "rayon"))]
+mod rayon_tests {
+    use crate::OmniMap;
+    use rayon::prelude::*;
+
+    fn make_map(len: u32) -> OmniMap<u32, u32> {
+        // Descending keys, so the insertion order differs from the natural order.
+        (0..len).rev().map(|i| (i, i * 10)).collect()
+    }
+
+    #[test]
+    fn test_par_iter_order() {
+        let map = make_map(10_000);
+
+        let entries: Vec<(&u32, &u32)> = map.par_iter().collect();
+        assert_eq!(entries, map.iter().collect::<Vec<_>>());
+
+        let keys: Vec<&u32> = map.par_keys().collect();
+        assert_eq!(keys, map.iter_keys().collect::<Vec<_>>());
+
+        let values: Vec<&u32> = map.par_values().collect();
+        assert_eq!(values, map.iter_values().collect::<Vec<_>>());
+
+        assert_eq!(map.par_iter().len(), 10_000);
+        assert_eq!(map.par_keys().rev().take(1).collect::<Vec<_>>(), vec![&0]);
+        assert_eq!(
+            map.par_iter().position_first(|(key, _)| *key == 9_000),
+            map.get_index_of(&9_000)
+        );
+
+        let empty: OmniMap<u32, u32> = OmniMap::new();
+        assert_eq!(empty.par_iter().count(), 0);
+        assert_eq!((&empty).into_par_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_par_iter_mut() {
+        let mut map = make_map(10_000);
+
+        map.par_iter_mut().for_each(|(key, value)| *value += key);
+        (&mut map)
+            .into_par_iter()
+            .for_each(|(_, value)| *value += 1);
+
+        for (key, value) in map.iter() {
+            assert_eq!(*value, key * 11 + 1);
+        }
+    }
+
+    #[test]
+    fn test_into_par_iter() {
+        let map = make_map(10_000);
+        let expected: Vec<(u32, u32)> = map.iter().map(|(k, v)| (*k, *v)).collect();
+
+        let entries: Vec<(u32, u32)> = map.into_par_iter().collect();
+        assert_eq!(entries, expected);
+    }
+
+    #[test]
+    fn test_from_par_iter_and_par_extend() {
+        let map: OmniMap<u32, u32> = (0..10_000u32).into_par_iter().map(|i| (i, i)).collect();
+
+        assert_eq!(map.len(), 10_000);
+        for (i, (key, value)) in map.iter().enumerate() {
+            assert_eq!(*key, i as u32);
+            assert_eq!(*value, i as u32);
+        }
+
+        // Duplicates keep the position of the first key, and take the last value.
+        let mut map = OmniMap::new();
+        map.insert(5, 0);
+        map.par_extend(vec![(1, 1), (5, 5), (2, 2), (1, 10)]);
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![(&5, &5), (&1, &10), (&2, &2)]
+        );
+
+        let other = make_map(3);
+        map.par_extend(other.par_iter());
+        assert_eq!(
+            map.iter_keys().copied().collect::<Vec<_>>(),
+            vec![5, 1, 2, 0]
+        );
+        assert_eq!(map.get(&2), Some(&20));
+    }
 }